@@ -0,0 +1,319 @@
+use anyhow::{Context, Result};
+use common::budget::BudgetExceeded;
+use serde::Deserialize;
+use std::{
+    fs,
+    io::Read,
+    path::{Component, Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// An input to solve with the solver of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    pub day: u8,
+    pub input: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum Status {
+    Solved,
+    Failed(String),
+    Panicked(String),
+    TimedOut,
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub answers: [Option<String>; 2],
+    pub elapsed: Duration,
+    pub status: Status,
+}
+
+// The day of an input from the first `dayN` prefix of its file name or of
+// one of its directories, e.g. `alice/day8.txt` or `day8/alice.txt`.
+fn day_of(path: &Path) -> Option<u8> {
+    path.components().rev().find_map(|c| match c {
+        Component::Normal(name) => {
+            let digits: String = name
+                .to_str()?
+                .strip_prefix("day")?
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+            digits.parse().ok().filter(|d| (1..=25).contains(d))
+        }
+        _ => None,
+    })
+}
+
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect(&path, files)?;
+        } else if path.extension().is_some_and(|e| e == "txt") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The `.txt` files under `dir` to solve. When a day is chosen, the inputs
+/// of other days are skipped and those of no day in particular are kept;
+/// otherwise every input is solved with the day found in its path.
+pub fn jobs(dir: &Path, day: Option<u8>) -> Result<Vec<Job>> {
+    let mut files = vec![];
+    collect(dir, &mut files)?;
+    files.sort();
+    let mut jobs = vec![];
+    for input in files {
+        let found = day_of(input.strip_prefix(dir).unwrap_or(&input));
+        match (day, found) {
+            (Some(day), Some(found)) if day != found => {}
+            (Some(day), _) | (None, Some(day)) => jobs.push(Job { day, input }),
+            (None, None) => eprintln!("skipping {}: no day in its path", input.display()),
+        }
+    }
+    Ok(jobs)
+}
+
+#[derive(Deserialize)]
+struct Line {
+    part: u8,
+    answer: String,
+}
+
+fn drain<R: Read + Send + 'static>(mut pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut content = String::new();
+        if let Some(pipe) = pipe.as_mut() {
+            let _ = pipe.read_to_string(&mut content);
+        }
+        content
+    })
+}
+
+// Waits for the child to exit, killing it after `timeout`. Returns `None`
+// when it had to be killed.
+fn wait(child: &mut Child, timeout: Duration) -> Result<Option<ExitStatus>> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if start.elapsed() >= timeout {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+// The messages of the error chain printed by `main`, on a single line.
+fn error_chain(stderr: &str) -> String {
+    let messages: Vec<&str> = stderr
+        .lines()
+        .take_while(|l| !l.starts_with("Stack backtrace:"))
+        .map(|l| l.trim().trim_start_matches("Error: "))
+        .filter(|l| !l.is_empty() && *l != "Caused by:")
+        .map(|l| match l.split_once(": ") {
+            Some((ind, rest)) if ind.parse::<usize>().is_ok() => rest,
+            _ => l,
+        })
+        .collect();
+    messages.join(": ")
+}
+
+/// The exit code of `aoc run` when a solver gave up because its budget was
+/// exceeded, which the batch reports as timed out.
+pub const OUT_OF_BUDGET: i32 = 3;
+
+/// Whether a solver gave up because its budget was exceeded.
+pub fn out_of_budget(err: &anyhow::Error) -> bool {
+    err.chain().any(|e| e.is::<BudgetExceeded>())
+}
+
+// How long a solver has to give up by itself once its budget is exceeded.
+const KILL_GRACE: Duration = Duration::from_secs(1);
+
+// How long the process solving both parts may run, each part having its own
+// budget of `timeout`.
+fn deadline(timeout: Duration) -> Duration {
+    timeout * 2 + KILL_GRACE
+}
+
+// Solves the job in a separate process, so that a solver that panics,
+// aborts or loops forever cannot take the whole batch down with it. Each
+// part is asked to stop at `timeout`, and the process is killed if it
+// takes longer than both together.
+fn execute(job: &Job, timeout: Duration) -> Result<Outcome> {
+    let start = Instant::now();
+    let mut child = Command::new(std::env::current_exe()?)
+        .args([
+            "run",
+            "--format",
+            "json",
+            "--day",
+            &job.day.to_string(),
+            "--timeout",
+            &timeout.as_secs().to_string(),
+            "--input",
+        ])
+        .arg(&job.input)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("starting a solver process")?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let exited = wait(&mut child, deadline(timeout))?;
+    let elapsed = start.elapsed();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    let mut answers = [None, None];
+    for line in stdout.lines() {
+        if let Ok(Line { part, answer }) = serde_json::from_str(line) {
+            if let Some(slot) = answers.get_mut(usize::from(part).wrapping_sub(1)) {
+                *slot = Some(answer);
+            }
+        }
+    }
+    let status = match exited {
+        None => Status::TimedOut,
+        Some(status) if status.success() => Status::Solved,
+        Some(status) if status.code() == Some(OUT_OF_BUDGET) => Status::TimedOut,
+        // the location of a panic is on the line of `panicked at`, the
+        // message on the next one
+        Some(_) => match stderr.split_once("panicked at ") {
+            Some((_, rest)) => Status::Panicked(rest.lines().take(2).collect::<Vec<_>>().join(" ")),
+            None => Status::Failed(error_chain(&stderr)),
+        },
+    };
+    Ok(Outcome {
+        answers,
+        elapsed,
+        status,
+    })
+}
+
+/// Solves the jobs on `workers` threads, each one running a process at a
+/// time. The outcomes are in the order of the jobs.
+pub fn execute_all(jobs: &[Job], workers: usize, timeout: Duration) -> Vec<Result<Outcome>> {
+    let next = AtomicUsize::new(0);
+    let mut outcomes: Vec<(usize, Result<Outcome>)> = thread::scope(|s| {
+        let handles: Vec<_> = (0..workers.max(1))
+            .map(|_| {
+                s.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let ind = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(job) = jobs.get(ind) else {
+                            break done;
+                        };
+                        done.push((ind, execute(job, timeout)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("a batch worker panicked"))
+            .collect()
+    });
+    outcomes.sort_by_key(|(ind, _)| *ind);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+/// Prints the answers and timings of the jobs as a table, returns the
+/// number of jobs that did not finish successfully.
+pub fn print_table(jobs: &[Job], outcomes: &[Result<Outcome>]) -> usize {
+    let mut rows = vec![[
+        String::from("Input"),
+        String::from("Day"),
+        String::from("Part 1"),
+        String::from("Part 2"),
+        String::from("Time"),
+        String::from("Status"),
+    ]];
+    let mut flagged = 0;
+    for (job, outcome) in jobs.iter().zip(outcomes) {
+        let input = job.input.display().to_string();
+        let day = job.day.to_string();
+        let row = match outcome {
+            Ok(outcome) => {
+                let status = match &outcome.status {
+                    Status::Solved => String::from("ok"),
+                    Status::Failed(msg) => format!("failed: {}", msg),
+                    Status::Panicked(msg) => format!("panicked at {}", msg),
+                    Status::TimedOut => String::from("timed out"),
+                };
+                let [part1, part2] = outcome
+                    .answers
+                    .clone()
+                    .map(|a| a.unwrap_or_else(|| String::from("-")));
+                [
+                    input,
+                    day,
+                    part1,
+                    part2,
+                    format!("{:.3?}", outcome.elapsed),
+                    status,
+                ]
+            }
+            Err(err) => [
+                input,
+                day,
+                String::from("-"),
+                String::from("-"),
+                String::from("-"),
+                format!("failed: {:#}", err),
+            ],
+        };
+        if !matches!(
+            outcome,
+            Ok(Outcome {
+                status: Status::Solved,
+                ..
+            })
+        ) {
+            flagged += 1;
+        }
+        rows.push(row);
+    }
+    let mut widths = [0; 6];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+    flagged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_from_paths() {
+        assert_eq!(day_of(Path::new("alice/day8.txt")), Some(8));
+        assert_eq!(day_of(Path::new("day12/bob.txt")), Some(12));
+        assert_eq!(day_of(Path::new("day3/day17-carol.txt")), Some(17));
+        assert_eq!(day_of(Path::new("day26/input.txt")), None);
+        assert_eq!(day_of(Path::new("dave/input.txt")), None);
+    }
+}
@@ -0,0 +1,396 @@
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use common::{
+    answers::{self, AnswerStore, Verdict},
+    bench::{self, Samples, Stats},
+    budget::{self, Budget, BudgetArgs},
+    generate,
+    output::{self, Format},
+    Answer, Part,
+};
+use serde::Serialize;
+use std::{fs, path::PathBuf, process, thread, time::Duration};
+
+mod batch;
+
+/// The entry points of a day's solver.
+struct Day {
+    solve: fn(&str, Part) -> Result<Answer>,
+    bench: fn(&str, usize) -> Result<Samples>,
+    generate: fn(u64, usize) -> String,
+}
+
+macro_rules! days {
+    ($($krate:ident::$solver:ident),* $(,)?) => {
+        [$(Day {
+            solve: common::solve::<$krate::$solver>,
+            bench: bench::measure::<$krate::$solver>,
+            generate: generate::generate::<$krate::$solver>,
+        }),*]
+    };
+}
+
+const DAYS: [Day; 25] = days![
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day20::Day20,
+    day21::Day21,
+    day22::Day22,
+    day23::Day23,
+    day24::Day24,
+    day25::Day25,
+];
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Writes debug output of the solvers to standard error
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves the puzzles of one day or of all of them
+    Run {
+        /// Day to solve, all days when omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Part to solve, both parts when omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input, dayN/input.txt when omitted
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+        /// How the answers are written
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+        #[command(flatten)]
+        budget: BudgetArgs,
+    },
+    /// Times the parsing and both parts of one day or of all of them
+    Bench {
+        /// Day to time, all days when omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Number of times every day is run
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        runs: u64,
+        /// File to write a JSON report to, for comparing runs across commits
+        #[arg(long)]
+        json: Option<PathBuf>,
+    },
+    /// Writes a random puzzle input for a day
+    Generate {
+        /// Day to write an input for
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Seed of the random generator, the same seed gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Scale of the puzzle, usually the number of lines or the side of the map
+        #[arg(long, default_value_t = 20)]
+        size: usize,
+        /// File to write the input to, standard output when omitted
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Checks the answers for dayN/input.txt and dayN/inputs/*.txt against
+    /// the known ones
+    Verify {
+        /// Day to check, all days when omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// JSON file with the known answers
+        #[arg(long, default_value = "answers.json")]
+        answers: PathBuf,
+        /// Adds the answers for inputs that are not known yet to the file
+        #[arg(long)]
+        record: bool,
+    },
+    /// Solves every input in a directory, several at the same time, and
+    /// prints a table of the answers
+    Batch {
+        /// Directory searched for .txt inputs
+        #[arg(long)]
+        dir: PathBuf,
+        /// Day to solve the inputs with, otherwise the dayN in their paths
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Seconds after which each part of a solver is stopped
+        #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: u64,
+        /// Number of inputs solved at the same time, the number of CPUs when
+        /// omitted
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        jobs: Option<u64>,
+    },
+}
+
+#[derive(Serialize)]
+struct Report {
+    runs: u64,
+    days: Vec<DayReport>,
+}
+
+#[derive(Serialize)]
+struct DayReport {
+    day: u8,
+    parse: PhaseReport,
+    part1: PhaseReport,
+    part2: PhaseReport,
+}
+
+/// Timings of a phase in nanoseconds.
+#[derive(Serialize)]
+struct PhaseReport {
+    median_ns: u64,
+    p95_ns: u64,
+}
+
+impl From<Stats> for PhaseReport {
+    fn from(stats: Stats) -> Self {
+        let nanos = |d: Duration| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX);
+        Self {
+            median_ns: nanos(stats.median),
+            p95_ns: nanos(stats.p95),
+        }
+    }
+}
+
+fn default_input(day: u8) -> PathBuf {
+    PathBuf::from(format!("day{}", day)).join("input.txt")
+}
+
+// The inputs of a day: the one of the repository and those of other people
+// collected in the inputs directory.
+fn inputs(day: u8) -> Result<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = vec![default_input(day)];
+    let dir = PathBuf::from(format!("day{}", day)).join("inputs");
+    if dir.is_dir() {
+        let mut others = vec![];
+        for entry in fs::read_dir(&dir).with_context(|| format!("reading {}", dir.display()))? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "txt") {
+                others.push(path);
+            }
+        }
+        others.sort();
+        paths.extend(others);
+    }
+    Ok(paths.into_iter().filter(|p| p.is_file()).collect())
+}
+
+#[derive(Default)]
+struct Tally {
+    pass: usize,
+    fail: usize,
+    unknown: usize,
+}
+
+fn verify(day: u8, store: &mut AnswerStore, record: bool, tally: &mut Tally) -> Result<()> {
+    for path in inputs(day)? {
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        let hash = answers::input_hash(&content);
+        for part in Part::ALL {
+            let label = format!("Day {:2} part {} {}", day, part.number(), path.display());
+            let answer = match (DAYS[day as usize - 1].solve)(&content, part) {
+                Ok(answer) => answer,
+                Err(err) => {
+                    println!("{}: fail, {:#}", label, err);
+                    tally.fail += 1;
+                    continue;
+                }
+            };
+            match store.check(day, part, &hash, &answer) {
+                Verdict::Pass => {
+                    println!("{}: pass", label);
+                    tally.pass += 1;
+                }
+                Verdict::Fail { expected } => {
+                    println!("{}: fail, expected {}, got {}", label, expected, answer);
+                    tally.fail += 1;
+                }
+                Verdict::Unknown => {
+                    println!("{}: unknown, got {}", label, answer);
+                    tally.unknown += 1;
+                    if record {
+                        store.insert(day, part, &hash, &answer);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run(day: u8, parts: &[Part], input: &PathBuf, format: Format, budget: Budget) -> Result<()> {
+    let content =
+        fs::read_to_string(input).with_context(|| format!("reading {}", input.display()))?;
+    for part in parts {
+        let answer =
+            budget::with_budget(budget, || (DAYS[day as usize - 1].solve)(&content, *part))?;
+        println!("{}", output::answer_line(format, day, *part, &answer));
+    }
+    Ok(())
+}
+
+fn bench(day: u8, runs: u64) -> Result<DayReport> {
+    let input = default_input(day);
+    let content =
+        fs::read_to_string(&input).with_context(|| format!("reading {}", input.display()))?;
+    let samples = (DAYS[day as usize - 1].bench)(&content, runs as usize)
+        .with_context(|| format!("timing day {}", day))?;
+    let phases = [
+        ("parse", &samples.parse),
+        ("part1", &samples.part1),
+        ("part2", &samples.part2),
+    ]
+    .map(|(name, durations)| (name, Stats::of(durations)));
+    for (name, stats) in phases {
+        println!(
+            "Day {:2} {}: median {:>12.3?}, p95 {:>12.3?}",
+            day, name, stats.median, stats.p95
+        );
+    }
+    let [(_, parse), (_, part1), (_, part2)] = phases;
+    Ok(DayReport {
+        day,
+        parse: parse.into(),
+        part1: part1.into(),
+        part2: part2.into(),
+    })
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    output::set_verbosity(cli.verbose);
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+            budget,
+        } => {
+            let budget = Budget::from(budget);
+            let parts = match part {
+                Some(part) => vec![Part::try_from(part)?],
+                None => Part::ALL.to_vec(),
+            };
+            let solved = match day {
+                Some(day) => {
+                    let input = input.unwrap_or_else(|| default_input(day));
+                    run(day, &parts, &input, format, budget)
+                }
+                None => (1..=25)
+                    .try_for_each(|day| run(day, &parts, &default_input(day), format, budget)),
+            };
+            // told apart from other failures by the batch
+            if let Err(err) = &solved {
+                if batch::out_of_budget(err) {
+                    eprintln!("Error: {:?}", err);
+                    process::exit(batch::OUT_OF_BUDGET);
+                }
+            }
+            solved
+        }
+        Command::Bench { day, runs, json } => {
+            let days = match day {
+                Some(day) => vec![bench(day, runs)?],
+                None => (1..=25)
+                    .map(|day| bench(day, runs))
+                    .collect::<Result<_>>()?,
+            };
+            if let Some(path) = json {
+                let report = serde_json::to_string_pretty(&Report { runs, days })?;
+                fs::write(&path, report + "\n")
+                    .with_context(|| format!("writing {}", path.display()))?;
+            }
+            Ok(())
+        }
+        Command::Generate {
+            day,
+            seed,
+            size,
+            output,
+        } => {
+            let input = (DAYS[day as usize - 1].generate)(seed, size);
+            match output {
+                Some(path) => {
+                    fs::write(&path, input).with_context(|| format!("writing {}", path.display()))
+                }
+                None => {
+                    print!("{}", input);
+                    Ok(())
+                }
+            }
+        }
+        Command::Verify {
+            day,
+            answers,
+            record,
+        } => {
+            let mut store = AnswerStore::load(&answers)?;
+            let mut tally = Tally::default();
+            let days = match day {
+                Some(day) => day..=day,
+                None => 1..=25,
+            };
+            for day in days {
+                verify(day, &mut store, record, &mut tally)?;
+                // saved after every day to keep what is known if a slow day
+                // is interrupted
+                if record && tally.unknown > 0 {
+                    store.save(&answers)?;
+                }
+            }
+            println!(
+                "{} passed, {} failed, {} unknown",
+                tally.pass, tally.fail, tally.unknown
+            );
+            if tally.fail > 0 {
+                anyhow::bail!("{} answers do not match the known ones", tally.fail);
+            }
+            Ok(())
+        }
+        Command::Batch {
+            dir,
+            day,
+            timeout,
+            jobs,
+        } => {
+            let todo = batch::jobs(&dir, day)?;
+            let workers = match jobs {
+                Some(jobs) => jobs as usize,
+                None => thread::available_parallelism().map_or(1, |n| n.get()),
+            };
+            let outcomes = batch::execute_all(&todo, workers, Duration::from_secs(timeout));
+            let flagged = batch::print_table(&todo, &outcomes);
+            if flagged > 0 {
+                anyhow::bail!("{} of {} inputs were not solved", flagged, todo.len());
+            }
+            Ok(())
+        }
+    }
+}
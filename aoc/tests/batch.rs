@@ -0,0 +1,25 @@
+use std::{path::Path, process::Command};
+
+#[test]
+fn part2_out_of_budget() {
+    // a 7 by 7 lattice of trails, the longest hike without the slopes takes
+    // minutes to find
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["batch", "--timeout", "1", "--dir"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/slow"))
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let row: Vec<&str> = stdout
+        .lines()
+        .find(|l| l.contains("day23.txt"))
+        .unwrap()
+        .split_whitespace()
+        .collect();
+    assert_eq!(row[1..4], ["23", "146", "-"]);
+    assert_eq!(row[5..], ["timed", "out"]);
+    // the solver gave up by itself, before the batch had to kill it
+    let elapsed: f64 = row[4].trim_end_matches('s').parse().unwrap();
+    assert!(elapsed < 3.0, "took {}s", elapsed);
+}
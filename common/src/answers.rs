@@ -0,0 +1,130 @@
+use crate::{Answer, Part};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path};
+
+/// A stable fingerprint of a puzzle input, the same for every platform,
+/// compiler version and line ending style: FNV-1a over the lines.
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for line in input.trim_end().lines() {
+        for byte in line.trim_end_matches('\r').bytes().chain([b'\n']) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Entry {
+    day: u8,
+    part: u8,
+    input: String,
+    answer: String,
+}
+
+/// The outcome of checking an answer against the store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+/// Answers known to be right, keyed by day, part and input hash, so that
+/// inputs of several people can be checked with the same file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AnswerStore {
+    answers: Vec<Entry>,
+}
+
+impl AnswerStore {
+    /// Reads the store from a JSON file, an empty store if there is no file.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("parsing answers in {}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    /// Writes the store as JSON, sorted so that diffs stay small.
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        self.answers
+            .sort_by(|a, b| (a.day, a.part, &a.input).cmp(&(b.day, b.part, &b.input)));
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content + "\n").with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: Part, hash: &str) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|e| e.day == day && e.part == part.number() && e.input == hash)
+            .map(|e| e.answer.as_str())
+    }
+
+    /// Records the answer for an input, replacing the one known before.
+    pub fn insert(&mut self, day: u8, part: Part, hash: &str, answer: &Answer) {
+        let answer = answer.to_string();
+        match self
+            .answers
+            .iter_mut()
+            .find(|e| e.day == day && e.part == part.number() && e.input == hash)
+        {
+            Some(entry) => entry.answer = answer,
+            None => self.answers.push(Entry {
+                day,
+                part: part.number(),
+                input: hash.to_string(),
+                answer,
+            }),
+        }
+    }
+
+    pub fn check(&self, day: u8, part: Part, hash: &str, answer: &Answer) -> Verdict {
+        match self.get(day, part, hash) {
+            Some(expected) if expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_ignores_line_endings() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a\nb\n"), input_hash("a\r\nb"));
+        assert_ne!(input_hash("a\nb\n"), input_hash("b\na\n"));
+    }
+
+    #[test]
+    fn verdicts() {
+        let mut store = AnswerStore::default();
+        let hash = input_hash("1abc2\n");
+        store.insert(1, Part::One, &hash, &Answer::Number(12));
+        assert_eq!(
+            store.check(1, Part::One, &hash, &Answer::Number(12)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            store.check(1, Part::One, &hash, &Answer::Number(13)),
+            Verdict::Fail {
+                expected: String::from("12")
+            }
+        );
+        assert_eq!(
+            store.check(1, Part::Two, &hash, &Answer::Number(12)),
+            Verdict::Unknown
+        );
+        store.insert(1, Part::One, &hash, &Answer::Number(13));
+        assert_eq!(store.get(1, Part::One, &hash), Some("13"));
+    }
+}
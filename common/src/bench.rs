@@ -0,0 +1,70 @@
+use crate::Solution;
+use anyhow::Result;
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Durations of the repeated runs of every phase of a solver.
+#[derive(Debug, Default)]
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+/// Parses `input` and solves both parts `runs` times, timing each phase
+/// separately.
+pub fn measure<S: Solution>(input: &str, runs: usize) -> Result<Samples> {
+    let mut samples = Samples::default();
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = black_box(S::parse(input)?);
+        samples.parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&parsed)?);
+        samples.part1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&parsed)?);
+        samples.part2.push(start.elapsed());
+    }
+    Ok(samples)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Median and 95th percentile (nearest rank) of the samples.
+    pub fn of(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let rank = |p: usize| {
+            let ind = (sorted.len() * p).div_ceil(100).max(1) - 1;
+            sorted.get(ind).copied().unwrap_or_default()
+        };
+        Self {
+            median: rank(50),
+            p95: rank(95),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::of(&samples);
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(Stats::of(&[]).median, Duration::ZERO);
+    }
+}
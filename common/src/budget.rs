@@ -0,0 +1,208 @@
+use std::{
+    cell::RefCell,
+    error, fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Limits on how long a solver may run. The long loops of the solvers
+/// check them through `tick`, there is no limit when both are `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    pub time: Option<Duration>,
+    pub steps: Option<u64>,
+}
+
+/// Command line options setting a `Budget`.
+#[derive(Debug, Clone, Copy, Default, clap::Args)]
+pub struct BudgetArgs {
+    /// Seconds after which a solver gives up
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+    /// Steps of its long loops after which a solver gives up
+    #[arg(long)]
+    pub max_steps: Option<u64>,
+}
+
+impl From<BudgetArgs> for Budget {
+    fn from(args: BudgetArgs) -> Self {
+        Self {
+            time: args.timeout.map(Duration::from_secs),
+            steps: args.max_steps,
+        }
+    }
+}
+
+/// The limit of a budget that was reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    Time(Duration),
+    Steps(u64),
+}
+
+/// Returned by `tick` when the budget is exhausted, with the progress the
+/// solver reported so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    pub limit: Limit,
+    pub steps: u64,
+    pub elapsed: Duration,
+    pub progress: String,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.limit {
+            Limit::Time(time) => write!(f, "budget of {:?} exceeded", time)?,
+            Limit::Steps(steps) => write!(f, "budget of {} steps exceeded", steps)?,
+        }
+        write!(
+            f,
+            " after {} steps in {:.3?}, {}",
+            self.steps, self.elapsed, self.progress
+        )
+    }
+}
+
+impl error::Error for BudgetExceeded {}
+
+// The steps are shared by the threads the budget was carried over to.
+#[derive(Clone)]
+struct Meter {
+    budget: Budget,
+    start: Instant,
+    steps: Arc<AtomicU64>,
+}
+
+thread_local! {
+    static METER: RefCell<Option<Meter>> = const { RefCell::new(None) };
+}
+
+// Puts back the budget of the enclosing `with_budget`, even on panics.
+struct Restore(Option<Meter>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        METER.set(self.0.take());
+    }
+}
+
+/// Runs `f` with `budget` applying to the `tick`s on the current thread.
+pub fn with_budget<T>(budget: Budget, f: impl FnOnce() -> T) -> T {
+    let meter = Meter {
+        budget,
+        start: Instant::now(),
+        steps: Arc::new(AtomicU64::new(0)),
+    };
+    let _restore = Restore(METER.replace(Some(meter)));
+    f()
+}
+
+/// The budget of the enclosing `with_budget` on the current thread, to carry
+/// over to other threads with `with_shared`.
+#[derive(Clone)]
+pub struct Shared(Option<Meter>);
+
+/// Shares the budget of the current thread, if any.
+pub fn share() -> Shared {
+    Shared(METER.with_borrow(|meter| meter.clone()))
+}
+
+/// Runs `f` with the `tick`s on the current thread counting against the
+/// shared budget, the steps and the time taken by every thread adding up.
+pub fn with_shared<T>(shared: &Shared, f: impl FnOnce() -> T) -> T {
+    let _restore = Restore(METER.replace(shared.0.clone()));
+    f()
+}
+
+/// Counts a step of a long loop and fails once the budget of the enclosing
+/// `with_budget` is exhausted. `progress` describes what was done so far,
+/// it is only called on failure.
+pub fn tick(progress: impl FnOnce() -> String) -> Result<(), BudgetExceeded> {
+    METER.with_borrow_mut(|meter| {
+        let Some(meter) = meter else {
+            return Ok(());
+        };
+        let steps = meter.steps.fetch_add(1, Ordering::Relaxed) + 1;
+        let limit = match meter.budget {
+            Budget {
+                steps: Some(max), ..
+            } if steps > max => Some(Limit::Steps(max)),
+            Budget {
+                time: Some(time), ..
+            } if meter.start.elapsed() > time => Some(Limit::Time(time)),
+            _ => None,
+        };
+        match limit {
+            Some(limit) => Err(BudgetExceeded {
+                limit,
+                steps: steps - 1,
+                elapsed: meter.start.elapsed(),
+                progress: progress(),
+            }),
+            None => Ok(()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(limit: u64) -> Result<u64, BudgetExceeded> {
+        for i in 0..limit {
+            tick(|| format!("counted to {}", i))?;
+        }
+        Ok(limit)
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(count(1000), Ok(1000));
+        let budget = Budget {
+            time: None,
+            steps: Some(10),
+        };
+        assert_eq!(with_budget(budget, || count(10)), Ok(10));
+        let err = with_budget(budget, || count(11)).unwrap_err();
+        assert_eq!(err.limit, Limit::Steps(10));
+        assert_eq!(err.steps, 10);
+        assert_eq!(err.progress, "counted to 10");
+        // the budget only applies inside `with_budget`
+        assert_eq!(count(1000), Ok(1000));
+    }
+
+    #[test]
+    fn time() {
+        let budget = Budget {
+            time: Some(Duration::ZERO),
+            steps: None,
+        };
+        let err = with_budget(budget, || count(u64::MAX)).unwrap_err();
+        assert_eq!(err.limit, Limit::Time(Duration::ZERO));
+        assert_eq!(err.steps, 0);
+    }
+
+    #[test]
+    fn shared() {
+        let budget = Budget {
+            time: None,
+            steps: Some(10),
+        };
+        let err = with_budget(budget, || {
+            let shared = share();
+            let counted = std::thread::spawn(move || with_shared(&shared, || count(6)))
+                .join()
+                .unwrap();
+            assert_eq!(counted, Ok(6));
+            count(5)
+        })
+        .unwrap_err();
+        assert_eq!(err.steps, 10);
+        // nothing to share outside `with_budget`
+        assert_eq!(with_shared(&share(), || count(1000)), Ok(1000));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{Part, Solution};
+use anyhow::{Context, Result};
+use rand::{seq::index::sample, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::ops::Range;
+
+/// `count` distinct random names of lowercase letters, all of the same
+/// length: `min_len` letters, or more when that many names would crowd the
+/// shorter ones.
+pub fn names<R: Rng>(rng: &mut R, count: usize, min_len: usize) -> Vec<String> {
+    let mut len = min_len.max(1);
+    let space = loop {
+        match 26_usize.checked_pow(len as u32) {
+            Some(space) if space / 2 >= count => break space,
+            Some(_) => len += 1,
+            None => break usize::MAX,
+        }
+    };
+    sample(rng, space, count)
+        .into_iter()
+        .map(|mut ind| {
+            let mut name = vec![b'a'; len];
+            for letter in name.iter_mut().rev() {
+                *letter += (ind % 26) as u8;
+                ind /= 26;
+            }
+            String::from_utf8(name).expect("names are ASCII letters")
+        })
+        .collect()
+}
+
+/// A solver that can make up valid and solvable puzzle inputs.
+pub trait Generate: Solution {
+    /// Writes a random puzzle input. `size` scales the puzzle, usually it is
+    /// the number of lines or the side of the map.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String;
+}
+
+/// The input generated for `seed`, the same one on every platform and run.
+pub fn generate<S: Generate>(seed: u64, size: usize) -> String {
+    S::generate(&mut ChaCha8Rng::seed_from_u64(seed), size)
+}
+
+/// The corners `(x, y)` of a closed path that never touches itself. The
+/// path goes around columns given as `(width, rise, fall)`: every column
+/// starts at most `rise` above the top of the previous one and ends at most
+/// `fall` below its top, so neighbouring columns always overlap. The shape is
+/// optionally transposed, and it is walked in either direction starting from
+/// the corner `start` modulo the number of corners.
+pub fn loop_corners(
+    columns: &[(i64, i64, i64)],
+    transpose: bool,
+    reverse: bool,
+    start: usize,
+) -> Vec<(i64, i64)> {
+    let mut top = vec![];
+    let mut bottom = vec![];
+    let (mut x, mut lo, mut hi) = (0, 0, 0);
+    for (ind, &(width, rise, fall)) in columns.iter().enumerate() {
+        if ind == 0 {
+            hi = 1 + fall;
+        } else {
+            let next = hi - 1 - rise;
+            hi = next.max(lo) + 1 + fall;
+            lo = next;
+        }
+        top.extend([(x, lo), (x + width, lo)]);
+        bottom.extend([(x, hi), (x + width, hi)]);
+        x += width;
+    }
+    let mut corners = top;
+    corners.extend(bottom.into_iter().rev());
+    corners.dedup();
+    if transpose {
+        corners.iter_mut().for_each(|(x, y)| (*x, *y) = (*y, *x));
+    }
+    if reverse {
+        corners.reverse();
+    }
+    // drop the points in the middle of straight lines
+    let turns = |a: (i64, i64), b: (i64, i64), c: (i64, i64)| {
+        (b.0 - a.0).signum() != (c.0 - b.0).signum() || (b.1 - a.1).signum() != (c.1 - b.1).signum()
+    };
+    let len = corners.len();
+    let corners: Vec<(i64, i64)> = (0..len)
+        .filter(|&ind| {
+            turns(
+                corners[(ind + len - 1) % len],
+                corners[ind],
+                corners[(ind + 1) % len],
+            )
+        })
+        .map(|ind| corners[ind])
+        .collect();
+    let len = corners.len();
+    (0..len).map(|ind| corners[(start + ind) % len]).collect()
+}
+
+/// A random `loop_corners` path around `columns` columns at most
+/// `max_width` wide and `max_height` high, with coordinates from 0.
+pub fn random_loop<R: Rng>(
+    rng: &mut R,
+    columns: usize,
+    max_width: i64,
+    max_height: i64,
+) -> Vec<(i64, i64)> {
+    let columns: Vec<(i64, i64, i64)> = (0..columns.max(1))
+        .map(|_| {
+            (
+                rng.gen_range(1..=max_width),
+                rng.gen_range(0..max_height),
+                rng.gen_range(0..max_height),
+            )
+        })
+        .collect();
+    let mut corners = loop_corners(&columns, rng.gen(), rng.gen(), rng.gen_range(0..usize::MAX));
+    let min_x = corners.iter().map(|c| c.0).min().unwrap_or(0);
+    let min_y = corners.iter().map(|c| c.1).min().unwrap_or(0);
+    corners
+        .iter_mut()
+        .for_each(|(x, y)| (*x, *y) = (*x - min_x, *y - min_y));
+    corners
+}
+
+/// Solves both parts of the inputs generated for `seeds`, to check that the
+/// generator only writes valid and solvable puzzles.
+pub fn solve_generated<S: Generate>(seeds: Range<u64>, size: usize) -> Result<()> {
+    for seed in seeds {
+        let input = generate::<S>(seed, size);
+        for part in Part::ALL {
+            crate::solve::<S>(&input, part)
+                .with_context(|| format!("generated with seed {} and size {}", seed, size))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,462 @@
+use crate::parse::Token;
+use anyhow::{bail, Result};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// Cell coordinates: row first, then column, both counted from the top-left
+/// corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub r: usize,
+    pub c: usize,
+}
+
+impl Pos {
+    pub fn new(r: usize, c: usize) -> Self {
+        Self { r, c }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Row and column deltas of a single step.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+}
+
+const AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid stored row by row in a single vector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from its rows, which must all be of the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, |r| r.len());
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (ind, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                bail!(
+                    "row {} has {} cells while the first one has {}",
+                    ind + 1,
+                    row.len(),
+                    width
+                );
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a grid with one line of the input per row, converting every
+    /// character token with `cell`. Empty lines are skipped.
+    pub fn parse(input: &str, mut cell: impl FnMut(Token<'_>) -> Result<T>) -> Result<Self> {
+        let rows = Token::lines(input)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().map(&mut cell).collect::<Result<Vec<T>>>())
+            .collect::<Result<Vec<_>>>()?;
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.r < self.height && pos.c < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.r * self.width + pos.c])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.r * self.width + pos.c])
+        } else {
+            None
+        }
+    }
+
+    /// The position `dr` rows and `dc` columns away from `pos`, if it is
+    /// still inside the grid.
+    pub fn offset(&self, pos: Pos, dr: isize, dc: isize) -> Option<Pos> {
+        let r = pos.r.checked_add_signed(dr)?;
+        let c = pos.c.checked_add_signed(dc)?;
+        let moved = Pos::new(r, c);
+        self.contains(moved).then_some(moved)
+    }
+
+    pub fn step(&self, pos: Pos, dir: Direction) -> Option<Pos> {
+        let (dr, dc) = dir.delta();
+        self.offset(pos, dr, dc)
+    }
+
+    /// Orthogonal neighbours of `pos` inside the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos` inside the grid.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        AROUND
+            .into_iter()
+            .filter_map(move |(dr, dc)| self.offset(pos, dr, dc))
+    }
+
+    /// Maps coordinates of the infinite plane tiled with copies of the grid
+    /// to the position in the grid itself.
+    pub fn wrap(&self, r: i64, c: i64) -> Pos {
+        Pos::new(
+            r.rem_euclid(self.height as i64) as usize,
+            c.rem_euclid(self.width as i64) as usize,
+        )
+    }
+
+    /// The cell at the given coordinates of the infinitely tiled plane.
+    pub fn get_wrapped(&self, r: i64, c: i64) -> &T {
+        &self[self.wrap(r, c)]
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| Pos::new(r, c)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, holding a cell matching `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, v)| predicate(v)).map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Read-only view of the grid looked at through `transform`.
+    pub fn view(&self, transform: Transform) -> View<'_, T> {
+        View {
+            grid: self,
+            transform,
+        }
+    }
+
+    /// Mutable view of the grid looked at through `transform`.
+    pub fn view_mut(&mut self, transform: Transform) -> ViewMut<'_, T> {
+        ViewMut {
+            grid: self,
+            transform,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s, |t| Ok(t.char()))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// One of the eight ways to lay a rectangle back onto its bounding box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Identity,
+    RotateCw,
+    Rotate180,
+    RotateCcw,
+    /// Upside down: the last row becomes the first one.
+    FlipVertical,
+    /// Mirrored: the last column becomes the first one.
+    FlipHorizontal,
+    /// Rows become columns.
+    Transpose,
+    /// Transposition along the other diagonal.
+    AntiTranspose,
+}
+
+impl Transform {
+    fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Transform::RotateCw
+                | Transform::RotateCcw
+                | Transform::Transpose
+                | Transform::AntiTranspose
+        )
+    }
+
+    /// Maps a position of the transformed view back to the original grid of
+    /// `height` rows and `width` columns.
+    pub fn source(self, pos: Pos, height: usize, width: usize) -> Pos {
+        let Pos { r, c } = pos;
+        match self {
+            Transform::Identity => Pos::new(r, c),
+            Transform::RotateCw => Pos::new(height - 1 - c, r),
+            Transform::Rotate180 => Pos::new(height - 1 - r, width - 1 - c),
+            Transform::RotateCcw => Pos::new(c, width - 1 - r),
+            Transform::FlipVertical => Pos::new(height - 1 - r, c),
+            Transform::FlipHorizontal => Pos::new(r, width - 1 - c),
+            Transform::Transpose => Pos::new(c, r),
+            Transform::AntiTranspose => Pos::new(height - 1 - c, width - 1 - r),
+        }
+    }
+}
+
+/// Zero-copy transformed view of a grid.
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    transform: Transform,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        if self.transform.swaps_axes() {
+            self.grid.height
+        } else {
+            self.grid.width
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        if self.transform.swaps_axes() {
+            self.grid.width
+        } else {
+            self.grid.height
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&'a T> {
+        (pos.r < self.height() && pos.c < self.width()).then(|| {
+            &self.grid[self
+                .transform
+                .source(pos, self.grid.height, self.grid.width)]
+        })
+    }
+}
+
+impl<T> Index<Pos> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the view", pos))
+    }
+}
+
+/// Zero-copy transformed view of a grid allowing to modify its cells.
+pub struct ViewMut<'a, T> {
+    grid: &'a mut Grid<T>,
+    transform: Transform,
+}
+
+impl<T> ViewMut<'_, T> {
+    pub fn width(&self) -> usize {
+        self.grid.view(self.transform).width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.view(self.transform).height()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.grid.view(self.transform).get(pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if pos.r < self.height() && pos.c < self.width() {
+            let source = self
+                .transform
+                .source(pos, self.grid.height, self.grid.width);
+            self.grid.get_mut(source)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Index<Pos> for ViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the view", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for ViewMut<'_, T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the view", pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[Pos::new(1, 0)], 'c');
+        assert_eq!(grid.to_string(), "ab\ncd\n");
+        assert!("ab\nc".parse::<Grid<char>>().is_err());
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(grid.neighbors4(Pos::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbors8(Pos::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 5);
+        assert_eq!(grid.step(Pos::new(1, 2), Direction::Right), None);
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid: Grid<char> = "ab\ncd".parse().unwrap();
+        assert_eq!(*grid.get_wrapped(-1, -1), 'd');
+        assert_eq!(*grid.get_wrapped(4, 3), 'b');
+    }
+
+    #[test]
+    fn views() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        let read = |t: Transform| {
+            let view = grid.view(t);
+            (0..view.height())
+                .map(|r| {
+                    (0..view.width())
+                        .map(|c| view[Pos::new(r, c)])
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert_eq!(read(Transform::Identity), "abc\ndef");
+        assert_eq!(read(Transform::RotateCw), "da\neb\nfc");
+        assert_eq!(read(Transform::Rotate180), "fed\ncba");
+        assert_eq!(read(Transform::RotateCcw), "cf\nbe\nad");
+        assert_eq!(read(Transform::FlipVertical), "def\nabc");
+        assert_eq!(read(Transform::FlipHorizontal), "cba\nfed");
+        assert_eq!(read(Transform::Transpose), "ad\nbe\ncf");
+        assert_eq!(read(Transform::AntiTranspose), "fc\neb\nda");
+    }
+}
@@ -0,0 +1,143 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use output::Format;
+use std::{fmt, fs, path::PathBuf};
+
+pub mod answers;
+pub mod bench;
+pub mod budget;
+pub mod generate;
+pub mod grid;
+pub mod numtheory;
+pub mod output;
+pub mod parse;
+
+/// One of the two halves of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => anyhow::bail!("part must be 1 or 2, got {}", value),
+        }
+    }
+}
+
+/// The answer produced by a solver for one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value.into())
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// A solver for a single day: parses the puzzle input once and answers
+/// both parts from the parsed representation.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Answer>;
+    fn part2(input: &Self::Input) -> Result<Answer>;
+}
+
+/// Parses `input` and solves the requested part with the solver `S`.
+pub fn solve<S: Solution>(input: &str, part: Part) -> Result<Answer> {
+    let parsed = S::parse(input).with_context(|| format!("parsing input for day {}", S::DAY))?;
+    match part {
+        Part::One => S::part1(&parsed),
+        Part::Two => S::part2(&parsed),
+    }
+    .with_context(|| format!("solving day {} part {}", S::DAY, part.number()))
+}
+
+#[derive(Parser)]
+struct Args {
+    /// Puzzle input
+    #[arg(default_value = "input.txt")]
+    input: PathBuf,
+    /// How the answers are written
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+    /// Writes debug output to standard error
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+    #[command(flatten)]
+    budget: budget::BudgetArgs,
+}
+
+/// Entry point shared by the per-day binaries: solves both parts for the
+/// input file passed as argument, or `input.txt` if there is none.
+pub fn main<S: Solution>() -> Result<()> {
+    let args = Args::parse();
+    output::set_verbosity(args.verbose);
+    let path = args.input.display();
+    let content = fs::read_to_string(&args.input).with_context(|| format!("reading {}", path))?;
+    let parsed = S::parse(&content).with_context(|| format!("parsing {}", path))?;
+    for part in Part::ALL {
+        let answer = budget::with_budget(args.budget.into(), || match part {
+            Part::One => S::part1(&parsed),
+            Part::Two => S::part2(&parsed),
+        })?;
+        println!(
+            "{}",
+            output::answer_line(args.format, S::DAY, part, &answer)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,402 @@
+use std::{
+    cmp::Ordering,
+    error, fmt,
+    ops::{Div, Rem},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumberTheoryError {
+    /// The result does not fit into the integer type.
+    Overflow,
+    /// A modulus of a congruence is not positive.
+    InvalidModulus(i128),
+    /// The congruences contradict each other.
+    NoSolution,
+    /// A fraction is divided by zero.
+    ZeroDenominator,
+}
+
+impl fmt::Display for NumberTheoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberTheoryError::Overflow => write!(f, "arithmetic overflow"),
+            NumberTheoryError::InvalidModulus(m) => {
+                write!(f, "modulus must be positive, got {}", m)
+            }
+            NumberTheoryError::NoSolution => write!(f, "the congruences have no common solution"),
+            NumberTheoryError::ZeroDenominator => write!(f, "zero denominator"),
+        }
+    }
+}
+
+impl error::Error for NumberTheoryError {}
+
+pub type Result<T> = std::result::Result<T, NumberTheoryError>;
+
+/// Integer types supported by `gcd` and the checked `lcm` functions.
+pub trait Integer: Copy + Eq + Rem<Output = Self> + Div<Output = Self> {
+    const ZERO: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// `None` for the smallest value of a signed type, whose absolute value
+    /// does not fit.
+    fn checked_abs(self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    (signed: $($s:ty),*; unsigned: $($u:ty),*) => {
+        $(
+            impl Integer for $s {
+                const ZERO: Self = 0;
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$s>::checked_mul(self, rhs)
+                }
+
+                fn checked_abs(self) -> Option<Self> {
+                    <$s>::checked_abs(self)
+                }
+            }
+        )*
+        $(
+            impl Integer for $u {
+                const ZERO: Self = 0;
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$u>::checked_mul(self, rhs)
+                }
+
+                fn checked_abs(self) -> Option<Self> {
+                    Some(self)
+                }
+            }
+        )*
+    };
+}
+
+integer!(signed: i32, i64, i128; unsigned: u32, u64, u128, usize);
+
+/// Greatest common divisor, always non-negative, or `Overflow` if it does
+/// not fit into `T`: only the smallest value of a signed type has a
+/// divisor that large.
+pub fn gcd<T: Integer>(a: T, b: T) -> Result<T> {
+    let abs = |v: T| v.checked_abs().ok_or(NumberTheoryError::Overflow);
+    let (mut a, mut b) = match (a.checked_abs(), b.checked_abs()) {
+        (Some(a), Some(b)) => (a, b),
+        // the remainder by the other value is smaller than it, so it fits
+        (None, Some(b)) if b != T::ZERO => (b, abs(a % b)?),
+        (Some(a), None) if a != T::ZERO => (a, abs(b % a)?),
+        _ => return Err(NumberTheoryError::Overflow),
+    };
+    while b != T::ZERO {
+        let t = b;
+        b = a % b;
+        a = t;
+    }
+    Ok(a)
+}
+
+/// Least common multiple, or `Overflow` if it does not fit into `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Result<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Ok(T::ZERO);
+    }
+    let abs = |v: T| v.checked_abs().ok_or(NumberTheoryError::Overflow);
+    (abs(a)? / gcd(a, b)?)
+        .checked_mul(abs(b)?)
+        .ok_or(NumberTheoryError::Overflow)
+}
+
+/// Least common multiple of all the values, 1 for none.
+pub fn checked_lcm_all<T: Integer + From<u8>>(values: impl IntoIterator<Item = T>) -> Result<T> {
+    values
+        .into_iter()
+        .try_fold(T::from(1), |acc, v| checked_lcm(acc, v))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g = gcd(a, b)`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// `a * b` modulo the positive `m`. When the product does not fit, it is
+// summed by doubling, with the operands kept below `m` all along.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    let (mut a, mut b) = (a.rem_euclid(m), b.rem_euclid(m));
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    // `x + y` modulo `m`, for `x` and `y` below it
+    let add = |x: i128, y: i128| if x >= m - y { x - (m - y) } else { x + y };
+    let mut product = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add(product, a);
+        }
+        a = add(a, a);
+        b >>= 1;
+    }
+    product
+}
+
+/// Solves the system of congruences `x = residue (mod modulus)` given as
+/// `(residue, modulus)` pairs. The moduli do not have to be coprime.
+///
+/// Returns `(x, m)` where `x` is the smallest non-negative solution and `m`
+/// is the least common multiple of the moduli, so every `x + k * m` is a
+/// solution as well.
+pub fn crt(congruences: &[(i128, i128)]) -> Result<(i128, i128)> {
+    let mut x = 0_i128;
+    let mut m = 1_i128;
+    for &(residue, modulus) in congruences {
+        if modulus <= 0 {
+            return Err(NumberTheoryError::InvalidModulus(modulus));
+        }
+        let residue = residue.rem_euclid(modulus);
+        // x + m * k = residue (mod modulus) => m * k = residue - x
+        let (g, inv, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return Err(NumberTheoryError::NoSolution);
+        }
+        let step = modulus / g;
+        let k = mul_mod(diff / g, inv, step);
+        let lcm = m.checked_mul(step).ok_or(NumberTheoryError::Overflow)?;
+        x = m
+            .checked_mul(k)
+            .and_then(|v| v.checked_add(x))
+            .ok_or(NumberTheoryError::Overflow)?
+            .rem_euclid(lcm);
+        m = lcm;
+    }
+    Ok((x, m))
+}
+
+/// The smallest solution of the congruences not less than `at_least`.
+pub fn crt_at_least(congruences: &[(i128, i128)], at_least: i128) -> Result<i128> {
+    let (x, m) = crt(congruences)?;
+    // both remainders are in `0..m`, so their difference cannot overflow
+    let ahead = (x - at_least.rem_euclid(m)).rem_euclid(m);
+    at_least
+        .checked_add(ahead)
+        .ok_or(NumberTheoryError::Overflow)
+}
+
+/// An exact fraction of `i128`s, kept in lowest terms with a positive
+/// denominator so that equal values compare equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+impl Ratio {
+    /// `num / den`, panics when `den` is zero or when the fraction does not
+    /// fit, see `checked_new`.
+    pub fn new(num: i128, den: i128) -> Self {
+        Self::checked_new(num, den).unwrap_or_else(|e| panic!("{}/{}: {}", num, den, e))
+    }
+
+    /// `num / den`, or `Overflow` when the denominator would be negative
+    /// and the numerator `i128::MIN` once in lowest terms.
+    pub fn checked_new(num: i128, den: i128) -> Result<Self> {
+        if den == 0 {
+            return Err(NumberTheoryError::ZeroDenominator);
+        }
+        if num == den {
+            return Ok(Self::from(1_i128));
+        }
+        let g = gcd(num, den)?;
+        let (num, den) = (num / g, den / g);
+        match den < 0 {
+            true => Ok(Self {
+                num: num.checked_neg().ok_or(NumberTheoryError::Overflow)?,
+                den: -den,
+            }),
+            false => Ok(Self { num, den }),
+        }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+}
+
+impl From<i128> for Ratio {
+    fn from(value: i128) -> Self {
+        Self { num: value, den: 1 }
+    }
+}
+
+impl From<i64> for Ratio {
+    fn from(value: i64) -> Self {
+        Self::from(i128::from(value))
+    }
+}
+
+impl Ord for Ratio {
+    // Compares the integer parts, then the fractional parts by their
+    // inverses, like the continued fractions of both: nothing is multiplied,
+    // so it works on any fractions.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b) = (self.num, self.den);
+        let (mut c, mut d) = (other.num, other.den);
+        loop {
+            let (int_a, int_c) = (a.div_euclid(b), c.div_euclid(d));
+            if int_a != int_c {
+                return int_a.cmp(&int_c);
+            }
+            let (rem_a, rem_c) = (a.rem_euclid(b), c.rem_euclid(d));
+            if rem_a == 0 || rem_c == 0 {
+                return rem_a.cmp(&rem_c);
+            }
+            // rem_a / b < rem_c / d when d / rem_c < b / rem_a
+            (a, b, c, d) = (d, rem_c, b, rem_a);
+        }
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.den {
+            1 => write!(f, "{}", self.num),
+            den => write!(f, "{}/{}", self.num, den),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12_i128, -18), Ok(6));
+        assert_eq!(gcd(0_u64, 7), Ok(7));
+        assert_eq!(gcd(i64::MIN, 12), Ok(4));
+        assert_eq!(gcd(-1, i128::MIN), Ok(1));
+        assert_eq!(gcd(i32::MIN, 0), Err(NumberTheoryError::Overflow));
+        assert_eq!(checked_lcm(i128::MIN, 3), Err(NumberTheoryError::Overflow));
+        assert_eq!(checked_lcm(4_i128, 6), Ok(12));
+        assert_eq!(
+            checked_lcm_all([4049_u128, 3761, 3931, 4079]),
+            Ok(244178746156661)
+        );
+        assert_eq!(
+            checked_lcm(u128::MAX, u128::MAX - 1),
+            Err(NumberTheoryError::Overflow)
+        );
+    }
+
+    #[test]
+    fn extended() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        // not coprime moduli
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Ok((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), Err(NumberTheoryError::NoSolution));
+        assert_eq!(crt(&[(1, 0)]), Err(NumberTheoryError::InvalidModulus(0)));
+        assert_eq!(crt_at_least(&[(0, 4), (0, 6)], 13), Ok(24));
+    }
+
+    #[test]
+    fn large_moduli() {
+        // the steps of the solution do not fit into an `i128` before being
+        // reduced
+        let (small, large) = (1_000_003, (1_i128 << 100) + 1);
+        let (x, m) = crt(&[(5, small), (large - 7, large)]).unwrap();
+        assert_eq!(m, small * large);
+        assert_eq!((x % small, x % large), (5, large - 7));
+
+        let max = i128::MAX;
+        let (x, m) = crt(&[(1, 2), (max / 2 - 1, max / 2)]).unwrap();
+        assert_eq!(m, max - 1);
+        assert_eq!((x % 2, x % (max / 2)), (1, max / 2 - 1));
+        assert_eq!(mul_mod(max - 1, max - 2, max), 2);
+        assert_eq!(mul_mod(-3, max - 1, max), 3);
+
+        assert_eq!(crt_at_least(&[(0, 2)], max - 1), Ok(max - 1));
+        assert_eq!(
+            crt_at_least(&[(0, 2)], max),
+            Err(NumberTheoryError::Overflow)
+        );
+        assert_eq!(crt_at_least(&[(1, 2)], i128::MIN + 1), Ok(i128::MIN + 1));
+        assert_eq!(crt_at_least(&[(1, 2)], i128::MIN), Ok(i128::MIN + 1));
+        assert_eq!(crt_at_least(&[(2, 5)], -9), Ok(-8));
+        assert_eq!(crt_at_least(&[(2, 5)], -8), Ok(-8));
+        assert_eq!(
+            crt(&[(0, max), (0, max - 1)]),
+            Err(NumberTheoryError::Overflow)
+        );
+    }
+
+    #[test]
+    fn ratios() {
+        let half = Ratio::new(-3, -6);
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(Ratio::new(4, -2), Ratio::from(-2_i64));
+        assert!(Ratio::new(-1, 3) < Ratio::new(-1, 4));
+        assert_eq!(Ratio::new(7, 2).to_string(), "7/2");
+        assert_eq!(Ratio::new(0, -5).to_string(), "0");
+
+        // the same order as the cross products when they fit
+        for (a, c) in (-6..=6).flat_map(|a| (-6..=6).map(move |c| (a, c))) {
+            for (b, d) in (1..=4).flat_map(|b| (1..=4).map(move |d| (b, d))) {
+                let order = Ratio::new(a, b).cmp(&Ratio::new(c, d));
+                assert_eq!(order, (a * d).cmp(&(c * b)));
+            }
+        }
+        // fractions whose cross products do not fit
+        let (max, min) = (i128::MAX, i128::MIN);
+        assert!(Ratio::new(max - 1, max) > Ratio::new(max - 2, max - 1));
+        assert!(Ratio::new(min, max) < Ratio::new(min + 1, max));
+        assert!(Ratio::new(max, 3) > Ratio::new(max - 1, 3));
+        assert_eq!(Ratio::new(min, min), Ratio::from(1_i128));
+        assert_eq!(Ratio::new(min, 2).denom(), 1);
+        assert_eq!(
+            Ratio::checked_new(min, -1),
+            Err(NumberTheoryError::Overflow)
+        );
+        assert_eq!(
+            Ratio::checked_new(1, 0),
+            Err(NumberTheoryError::ZeroDenominator)
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Answer, Part};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Sets how much debug output the solvers write to standard error, nothing
+/// at all with 0.
+pub fn set_verbosity(level: u8) {
+    VERBOSITY.store(level, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Writes a line to standard error when the verbosity is at least 1, for
+/// intermediate results that help understanding a solver but are not
+/// answers.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::output::verbosity() > 0 {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// How answers are written to standard output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// `Day 8 part 2: <answer>`
+    #[default]
+    Text,
+    /// One object per line, `{"day":8,"part":2,"answer":"<answer>"}`
+    Json,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: u8,
+    answer: &'a str,
+}
+
+/// The line reporting the answer to a part of a day's puzzle.
+pub fn answer_line(format: Format, day: u8, part: Part, answer: &Answer) -> String {
+    match format {
+        Format::Text => format!("Day {} part {}: {}", day, part.number(), answer),
+        Format::Json => serde_json::to_string(&Record {
+            day,
+            part: part.number(),
+            answer: &answer.to_string(),
+        })
+        .expect("a record always serializes"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines() {
+        let answer = Answer::Number(42);
+        assert_eq!(
+            answer_line(Format::Text, 8, Part::Two, &answer),
+            "Day 8 part 2: 42"
+        );
+        assert_eq!(
+            answer_line(Format::Json, 8, Part::Two, &answer),
+            r#"{"day":8,"part":2,"answer":"42"}"#
+        );
+        assert_eq!(
+            answer_line(
+                Format::Json,
+                25,
+                Part::Two,
+                &Answer::Text(String::from("\"*\""))
+            ),
+            r#"{"day":25,"part":2,"answer":"\"*\""}"#
+        );
+    }
+}
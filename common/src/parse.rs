@@ -0,0 +1,153 @@
+use std::{fmt, str::FromStr};
+
+/// A piece of the puzzle input that remembers where it was found, so that
+/// parse errors can point at the offending text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+/// An owned copy of the location and the text of a token, to be stored in
+/// parse errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` at line {}, column {}",
+            self.token, self.line, self.column
+        )
+    }
+}
+
+impl<'a> Token<'a> {
+    /// All the lines of the input with the surrounding whitespace trimmed,
+    /// including the empty ones. Lines and columns are counted from 1.
+    pub fn lines(input: &'a str) -> impl Iterator<Item = Token<'a>> {
+        input.lines().enumerate().map(|(ind, line)| {
+            Token {
+                text: line,
+                line: ind + 1,
+                column: 1,
+            }
+            .trim()
+        })
+    }
+
+    pub fn text(self) -> &'a str {
+        self.text
+    }
+
+    pub fn line(self) -> usize {
+        self.line
+    }
+
+    pub fn column(self) -> usize {
+        self.column
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn span(self) -> Span {
+        Span {
+            line: self.line,
+            column: self.column,
+            token: self.text.to_string(),
+        }
+    }
+
+    // `part` must be a subslice of `self.text`.
+    fn sub(self, part: &'a str) -> Token<'a> {
+        let offset = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        Token {
+            text: part,
+            line: self.line,
+            column: self.column + self.text[..offset].chars().count(),
+        }
+    }
+
+    pub fn trim(self) -> Self {
+        self.sub(self.text.trim())
+    }
+
+    /// The empty token right after this one, for reporting missing parts.
+    pub fn end(self) -> Self {
+        self.sub(&self.text[self.text.len()..])
+    }
+
+    pub fn split(self, separator: &'a str) -> impl Iterator<Item = Token<'a>> {
+        self.text.split(separator).map(move |part| self.sub(part))
+    }
+
+    pub fn split_once(self, separator: &str) -> Option<(Token<'a>, Token<'a>)> {
+        let (lhs, rhs) = self.text.split_once(separator)?;
+        Some((self.sub(lhs), self.sub(rhs)))
+    }
+
+    /// Whitespace separated words.
+    pub fn words(self) -> impl Iterator<Item = Token<'a>> {
+        self.text.split_whitespace().map(move |part| self.sub(part))
+    }
+
+    /// Every character as a token of its own.
+    pub fn chars(self) -> impl Iterator<Item = Token<'a>> {
+        self.text
+            .char_indices()
+            .map(move |(ind, ch)| self.sub(&self.text[ind..ind + ch.len_utf8()]))
+    }
+
+    pub fn strip_prefix(self, prefix: &str) -> Option<Self> {
+        self.text.strip_prefix(prefix).map(|rest| self.sub(rest))
+    }
+
+    pub fn strip_suffix(self, suffix: &str) -> Option<Self> {
+        self.text.strip_suffix(suffix).map(|rest| self.sub(rest))
+    }
+
+    /// The first character, `'\0'` for an empty token.
+    pub fn char(self) -> char {
+        self.text.chars().next().unwrap_or('\0')
+    }
+
+    /// Parses the text, returning the span of the token if it is invalid.
+    pub fn parse<T: FromStr>(self) -> Result<T, Span> {
+        self.text.parse().map_err(|_| self.span())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let lines: Vec<Token> = Token::lines("a: 1 2\n\n  b:  x").collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].is_empty());
+
+        let (name, values) = lines[2].split_once(":").unwrap();
+        assert_eq!((name.text(), name.line(), name.column()), ("b", 3, 3));
+        let value = values.words().next().unwrap();
+        assert_eq!(value.column(), 7);
+        assert_eq!(
+            value.parse::<u32>(),
+            Err(Span {
+                line: 3,
+                column: 7,
+                token: "x".to_string()
+            })
+        );
+        assert_eq!(lines[0].end().column(), 7);
+        assert_eq!(lines[0].chars().nth(3).map(|t| t.column()), Some(4));
+    }
+}
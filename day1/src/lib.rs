@@ -0,0 +1,133 @@
+use anyhow::Result;
+use common::{generate::Generate, Answer, Solution};
+use rand::Rng;
+
+const SPELLED: &str = "one two three four five six seven eight nine";
+
+fn value(s: &str, digit_sets: &[&str]) -> usize {
+    let mut result = 0;
+    let mut fst = s.len();
+    let mut lst = 0;
+    for digits in digit_sets {
+        for (v, digit) in digits.split(' ').enumerate() {
+            if let Some(ind) = s.find(digit) {
+                if ind <= fst {
+                    fst = ind;
+                    result = (v + 1) * 10 + result % 10;
+                }
+            }
+            if let Some(ind) = s.rfind(digit) {
+                if ind >= lst {
+                    lst = ind;
+                    result = (v + 1) + result / 10 * 10;
+                }
+            }
+        }
+    }
+    result
+}
+
+fn calibrate(lines: &[String], digit_sets: &[&str]) -> usize {
+    lines.iter().map(|s| value(s, digit_sets)).sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(calibrate(input, &["1 2 3 4 5 6 7 8 9"]).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(calibrate(input, &["1 2 3 4 5 6 7 8 9", SPELLED]).into())
+    }
+}
+
+impl Generate for Day1 {
+    // Letters mixed with digits and spelled digits, with at least one real
+    // digit on every line.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let mut r = String::new();
+        for _ in 0..size.max(1) {
+            let mut pieces: Vec<String> = (0..rng.gen_range(1..6))
+                .map(|_| match rng.gen_range(0..3) {
+                    0 => rng.gen_range(1..=9).to_string(),
+                    1 => SPELLED
+                        .split(' ')
+                        .nth(rng.gen_range(0..9))
+                        .unwrap()
+                        .to_string(),
+                    _ => (0..rng.gen_range(1..4))
+                        .map(|_| rng.gen_range(b'a'..=b'z') as char)
+                        .collect(),
+                })
+                .collect();
+            let ind = rng.gen_range(0..=pieces.len());
+            pieces.insert(ind, rng.gen_range(1..=9).to_string());
+            r += &pieces.concat();
+            r.push('\n');
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE1: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+    const EXAMPLE2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day1>(EXAMPLE1, Part::One).unwrap(),
+            Answer::Number(142)
+        );
+        assert_eq!(
+            solve::<Day1>(EXAMPLE2, Part::Two).unwrap(),
+            Answer::Number(281)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day1>(input, Part::One).unwrap(),
+            Answer::Number(52974)
+        );
+        assert_eq!(
+            solve::<Day1>(input, Part::Two).unwrap(),
+            Answer::Number(53340)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day1>(0..10, 50).unwrap();
+    }
+}
@@ -0,0 +1,363 @@
+use anyhow::Result;
+use common::{
+    generate::{random_loop, Generate},
+    grid::{Direction, Grid, Pos},
+    parse::Span,
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{cmp::Ordering, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedTile(Span),
+    MissingStart,
+    UnconnectedStart(Span),
+    BrokenLoop(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedTile(s) => write!(f, "unexpected tile {}", s),
+            ParseError::MissingStart => write!(f, "there is no starting tile S"),
+            ParseError::UnconnectedStart(s) => {
+                write!(f, "the starting tile {} is not connected to two pipes", s)
+            }
+            ParseError::BrokenLoop(s) => {
+                write!(f, "the loop does not lead back to S after {}", s)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn find_starting_pos(map: &Grid<char>) -> Result<Pos, ParseError> {
+    map.find(|c| *c == 'S').ok_or(ParseError::MissingStart)
+}
+
+fn span(map: &Grid<char>, pos: Pos) -> Span {
+    Span {
+        line: pos.r + 1,
+        column: pos.c + 1,
+        token: map[pos].to_string(),
+    }
+}
+
+fn connections(pipe: char) -> &'static [Direction] {
+    match pipe {
+        '|' => &[Direction::Up, Direction::Down],
+        '-' => &[Direction::Right, Direction::Left],
+        'L' => &[Direction::Up, Direction::Right],
+        'J' => &[Direction::Up, Direction::Left],
+        '7' => &[Direction::Down, Direction::Left],
+        'F' => &[Direction::Right, Direction::Down],
+        _ => &[],
+    }
+}
+
+// The pipe hidden under S, deduced from the neighbours connected to it.
+fn starting_pipe(map: &Grid<char>, start: Pos) -> Result<char, ParseError> {
+    let connected: Vec<Direction> = Direction::ALL
+        .into_iter()
+        .filter(|d| {
+            map.step(start, *d)
+                .is_some_and(|n| connections(map[n]).contains(&d.opposite()))
+        })
+        .collect();
+    PIPES
+        .into_iter()
+        .find(|p| connections(*p).iter().all(|d| connected.contains(d)))
+        .ok_or_else(|| ParseError::UnconnectedStart(span(map, start)))
+}
+
+fn step(start: Pos, dir: Direction, map: &Grid<char>) -> Direction {
+    let pipe = map[start];
+    match (pipe, dir) {
+        ('S', _) => dir,
+        ('|', _) => dir,
+        ('-', _) => dir,
+        ('L', Direction::Down) => Direction::Right,
+        ('L', _) => Direction::Up,
+        ('J', Direction::Down) => Direction::Left,
+        ('J', _) => Direction::Up,
+        ('7', Direction::Up) => Direction::Left,
+        ('7', _) => Direction::Down,
+        ('F', Direction::Up) => Direction::Right,
+        ('F', _) => Direction::Down,
+        _ => panic!("Encountered land"),
+    }
+}
+
+// Walks the loop from the starting position, returns its length and the
+// mask of the cells belonging to it. Every pipe entered must connect back to
+// the one before, so the walk can only end at S.
+fn trace_loop(map: &Grid<char>) -> Result<(usize, Grid<bool>), ParseError> {
+    let mut on_path = Grid::new(map.width(), map.height(), false);
+    let start = find_starting_pos(map)?;
+    on_path[start] = true;
+    let mut pos = start;
+    let mut steps = 0;
+    let mut dir = connections(starting_pipe(map, start)?)[0];
+    while steps == 0 || pos != start {
+        steps += 1;
+        dir = step(pos, dir, map);
+        pos = map
+            .step(pos, dir)
+            .filter(|&n| n == start || connections(map[n]).contains(&dir.opposite()))
+            .ok_or_else(|| ParseError::BrokenLoop(span(map, pos)))?;
+        on_path[pos] = true;
+    }
+    Ok((steps, on_path))
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse(input, |t| match t.char() {
+            c @ ('|' | '-' | 'L' | 'J' | '7' | 'F' | '.' | 'S') => Ok(c),
+            _ => Err(ParseError::UnexpectedTile(t.span()).into()),
+        })?;
+        trace_loop(&map)?;
+        Ok(map)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        let (steps, _) = trace_loop(input)?;
+        Ok(steps.div_ceil(2).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let (_, on_path) = trace_loop(input)?;
+        let start = find_starting_pos(input)?;
+        let mut map = input.clone();
+
+        // do simple scan fill algorithm, the only challenge is '-' lines.
+        map[start] = starting_pipe(input, start)?;
+        let mut part2 = 0_usize;
+        for i in 0..map.height() {
+            let mut outside = true;
+            let mut open = ' ';
+            for j in 0..map.width() {
+                let pos = Pos::new(i, j);
+                if on_path[pos] && open != ' ' {
+                    match (open, map[pos]) {
+                        ('F', '7') => open = ' ',
+                        ('L', 'J') => open = ' ',
+                        (_, '-') => {}
+                        _ => {
+                            outside = !outside;
+                            open = ' ';
+                        }
+                    };
+                } else if on_path[pos] {
+                    match map[pos] {
+                        'F' => open = 'F',
+                        'L' => open = 'L',
+                        '|' => {
+                            open = ' ';
+                            outside = !outside;
+                        }
+                        _ => {}
+                    }
+                } else {
+                    map[pos] = if outside { 'o' } else { 'X' };
+                    if !outside {
+                        part2 += 1;
+                    }
+                }
+            }
+        }
+        common::debug!("{}", map);
+        Ok(part2.into())
+    }
+}
+
+const PIPES: [char; 6] = ['|', '-', 'L', 'J', '7', 'F'];
+
+// The direction of the neighbouring cell `to`.
+fn towards(from: Pos, to: Pos) -> Direction {
+    match (to.r.cmp(&from.r), to.c.cmp(&from.c)) {
+        (Ordering::Less, _) => Direction::Up,
+        (Ordering::Greater, _) => Direction::Down,
+        (_, Ordering::Less) => Direction::Left,
+        _ => Direction::Right,
+    }
+}
+
+impl Generate for Day10 {
+    // A loop that never touches itself among random pipes, with S somewhere
+    // on it. The pipes next to S only point at it if they are on the loop, so
+    // that the pipe under S can be deduced.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let corners = random_loop(rng, size / 3 + 1, 3, 3);
+        let mut cells = vec![];
+        for (ind, &(x, y)) in corners.iter().enumerate() {
+            let (nx, ny) = corners[(ind + 1) % corners.len()];
+            let (mut cx, mut cy) = (x, y);
+            while (cx, cy) != (nx, ny) {
+                cells.push(Pos::new(cy as usize + 1, cx as usize + 1));
+                cx += (nx - x).signum();
+                cy += (ny - y).signum();
+            }
+        }
+        let width = cells.iter().map(|p| p.c).max().unwrap_or(0) + 2;
+        let height = cells.iter().map(|p| p.r).max().unwrap_or(0) + 2;
+        let mut map = Grid::new(width, height, '.');
+        let mut on_loop = Grid::new(width, height, false);
+        for pos in map.positions() {
+            if rng.gen_bool(0.8) {
+                map[pos] = *PIPES.choose(rng).unwrap();
+            }
+        }
+        for (ind, &pos) in cells.iter().enumerate() {
+            let prev = towards(pos, cells[(ind + cells.len() - 1) % cells.len()]);
+            let next = towards(pos, cells[(ind + 1) % cells.len()]);
+            map[pos] = PIPES
+                .into_iter()
+                .find(|p| connections(*p).contains(&prev) && connections(*p).contains(&next))
+                .unwrap();
+            on_loop[pos] = true;
+        }
+        let start = *cells.choose(rng).unwrap();
+        map[start] = 'S';
+        for dir in Direction::ALL {
+            if let Some(n) = map.step(start, dir).filter(|n| !on_loop[*n]) {
+                map[n] = '.';
+            }
+        }
+        map.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const SQUARE: &str = "\
+.....
+.S-7.
+.|.|.
+.L-J.
+.....
+";
+
+    const COMPLEX: &str = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...
+";
+
+    const ENCLOSED: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+";
+
+    const LARGER: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+";
+
+    const JUNK: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day10>(SQUARE, Part::One).unwrap(),
+            Answer::Number(4)
+        );
+        assert_eq!(
+            solve::<Day10>(COMPLEX, Part::One).unwrap(),
+            Answer::Number(8)
+        );
+        assert_eq!(
+            solve::<Day10>(ENCLOSED, Part::Two).unwrap(),
+            Answer::Number(4)
+        );
+        assert_eq!(
+            solve::<Day10>(LARGER, Part::Two).unwrap(),
+            Answer::Number(8)
+        );
+        assert_eq!(solve::<Day10>(JUNK, Part::Two).unwrap(), Answer::Number(10));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |input: &str| {
+            Day10::parse(input)
+                .err()
+                .and_then(|e| e.downcast::<ParseError>().ok())
+        };
+        assert_eq!(err("...\n.F.\n"), Some(ParseError::MissingStart));
+        assert_eq!(
+            err(".S.\n"),
+            Some(ParseError::UnconnectedStart(Span {
+                line: 1,
+                column: 2,
+                token: "S".to_string()
+            }))
+        );
+        // the pipe going down from S leads off the map
+        assert_eq!(
+            err("S-7\n|.|\n"),
+            Some(ParseError::BrokenLoop(Span {
+                line: 2,
+                column: 3,
+                token: "|".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day10>(input, Part::One).unwrap(),
+            Answer::Number(6725)
+        );
+        assert_eq!(
+            solve::<Day10>(input, Part::Two).unwrap(),
+            Answer::Number(383)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day10>(0..10, 30).unwrap();
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::Result;
+use common::{generate::Generate, grid::Grid, parse::Span, Answer, Solution};
+use rand::Rng;
+use std::{collections::HashSet, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedTile(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedTile(s) => write!(f, "unexpected tile {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn analyze(world: &Grid<char>) -> (Vec<usize>, Vec<usize>) {
+    let mut result = (vec![], vec![]);
+    let mut column_empty = vec![true; world.width()];
+    for (ri, row) in world.rows().enumerate() {
+        let mut empty = true;
+        for (ci, v) in row.iter().enumerate() {
+            if *v != '.' {
+                empty = false;
+                column_empty[ci] = false;
+            }
+        }
+        if empty {
+            result.0.push(ri);
+        }
+    }
+    result.1 = column_empty
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| **v)
+        .map(|(ci, _)| ci)
+        .collect();
+    result
+}
+
+fn enumerate_galaxies(
+    world: &Grid<char>,
+    empty_rows: &HashSet<usize>,
+    empty_columns: &HashSet<usize>,
+    speed: usize,
+) -> Vec<(usize, usize)> {
+    let mut galaxies: Vec<(usize, usize)> = vec![];
+    let mut grown_rows = 0;
+    for (ri, row) in world.rows().enumerate() {
+        if empty_rows.contains(&ri) {
+            grown_rows += 1;
+        }
+        let mut grown_columns = 0;
+        for (ci, v) in row.iter().enumerate() {
+            if empty_columns.contains(&ci) {
+                grown_columns += 1;
+            }
+            if *v != '.' {
+                galaxies.push((
+                    ri + grown_rows * (speed - 1),
+                    ci + grown_columns * (speed - 1),
+                ));
+            }
+        }
+    }
+    galaxies
+}
+
+fn calculate_distances(galaxies: &[(usize, usize)]) -> u64 {
+    let mut sum = 0;
+    for f in 1..galaxies.len() {
+        for t in 0..f {
+            sum += (galaxies[t].0 as i128 - galaxies[f].0 as i128).abs();
+            sum += (galaxies[t].1 as i128 - galaxies[f].1 as i128).abs();
+        }
+    }
+    sum as u64
+}
+
+fn expanded_distances(world: &Grid<char>, speed: usize) -> u64 {
+    let (empty_rows, empty_columns) = analyze(world);
+    let er: HashSet<usize> = empty_rows.into_iter().collect();
+    let ec: HashSet<usize> = empty_columns.into_iter().collect();
+    calculate_distances(&enumerate_galaxies(world, &er, &ec, speed))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse(input, |t| match t.char() {
+            c @ ('.' | '#') => Ok(c),
+            _ => Err(ParseError::UnexpectedTile(t.span()).into()),
+        })?;
+        Ok(map)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(expanded_distances(input, 2).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(expanded_distances(input, 1000000).into())
+    }
+}
+
+impl Generate for Day11 {
+    // Galaxies scattered over all but some random rows and columns, which
+    // stay empty and get expanded.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let side = size.max(2);
+        let empty_rows: Vec<bool> = (0..side).map(|_| rng.gen_bool(0.1)).collect();
+        let empty_cols: Vec<bool> = (0..side).map(|_| rng.gen_bool(0.1)).collect();
+        let mut map = Grid::new(side, side, '.');
+        for pos in map.positions() {
+            if !empty_rows[pos.r] && !empty_cols[pos.c] && rng.gen_bool(0.05) {
+                map[pos] = '#';
+            }
+        }
+        map.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day11>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(374)
+        );
+    }
+
+    #[test]
+    fn example_expansion() {
+        let world = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(expanded_distances(&world, 10), 1030);
+        assert_eq!(expanded_distances(&world, 100), 8410);
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day11>(input, Part::One).unwrap(),
+            Answer::Number(9623138)
+        );
+        assert_eq!(
+            solve::<Day11>(input, Part::Two).unwrap(),
+            Answer::Number(726820169514)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day11>(0..10, 40).unwrap();
+    }
+}
@@ -0,0 +1,243 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{collections::HashMap, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedSpring(Span),
+    MissingLengths(Span),
+    InvalidLength(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedSpring(s) => write!(f, "unexpected spring {}", s),
+            ParseError::MissingLengths(s) => write!(f, "missing group lengths after {}", s),
+            ParseError::InvalidLength(s) => write!(f, "invalid group length {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn variants(mask: &str, lengths: &[usize], mem: &mut HashMap<(usize, usize), usize>) -> usize {
+    if lengths.is_empty() {
+        return usize::from(!mask.contains('#'));
+    }
+    if let Some(r) = mem.get(&(mask.len(), lengths.len())) {
+        return *r;
+    }
+    let mut result = 0;
+    let after: usize = lengths.iter().skip(1).sum::<usize>() + lengths.len() - 1;
+    let Some(last) = mask.len().checked_sub(lengths[0] + after) else {
+        return 0;
+    };
+    for s in 0..=last {
+        if s > 0 && mask.chars().nth(s - 1).unwrap() == '#' {
+            break;
+        }
+        if after > 0 && mask.chars().nth(s + lengths[0]).unwrap() == '#' {
+            continue;
+        }
+        if mask[s..s + lengths[0]].contains(".") {
+            continue;
+        }
+        result += if lengths.len() == 1 {
+            if mask[s + lengths[0]..].contains('#') {
+                0
+            } else {
+                1
+            }
+        } else {
+            variants(&mask[s + lengths[0] + 1..], &lengths[1..], mem)
+        }
+    }
+    mem.insert((mask.len(), lengths.len()), result);
+    result
+}
+
+fn unfold(mask: &str, lengths: &[usize]) -> usize {
+    let length = 5 * lengths.len();
+    variants(
+        std::iter::repeat_n(mask, 5)
+            .collect::<Vec<_>>()
+            .join("?")
+            .as_str(),
+        &lengths
+            .iter()
+            .cycle()
+            .take(length)
+            .copied()
+            .collect::<Vec<usize>>()[..],
+        &mut HashMap::new(),
+    )
+}
+
+fn record(line: Token) -> Result<(String, Vec<usize>), ParseError> {
+    let mut parts = line.words();
+    let mask = parts
+        .next()
+        .ok_or_else(|| ParseError::MissingLengths(line.span()))?;
+    if let Some(spring) = mask.chars().find(|c| !matches!(c.char(), '.' | '#' | '?')) {
+        return Err(ParseError::UnexpectedSpring(spring.span()));
+    }
+    let lengths = parts
+        .next()
+        .ok_or_else(|| ParseError::MissingLengths(mask.span()))?
+        .split(",")
+        .map(|s| s.parse().map_err(ParseError::InvalidLength))
+        .collect::<Result<_, _>>()?;
+    Ok((mask.text().to_string(), lengths))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Vec<(String, Vec<usize>)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Token::lines(input)
+            .filter(|l| !l.is_empty())
+            .map(record)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(input
+            .iter()
+            .map(|(mask, lengths)| variants(mask, lengths, &mut HashMap::new()))
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(input
+            .iter()
+            .map(|(mask, lengths)| unfold(mask, lengths))
+            .sum::<usize>()
+            .into())
+    }
+}
+
+impl Generate for Day12 {
+    // Rows of damaged groups with some of the springs hidden behind `?`.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let mut r = String::new();
+        for _ in 0..size.max(1) {
+            let lengths: Vec<usize> = (0..rng.gen_range(1..=6))
+                .map(|_| rng.gen_range(1..=5))
+                .collect();
+            let mut row = ".".repeat(rng.gen_range(0..3));
+            for (ind, length) in lengths.iter().enumerate() {
+                if ind > 0 {
+                    row += &".".repeat(rng.gen_range(1..3));
+                }
+                row += &"#".repeat(*length);
+            }
+            row += &".".repeat(rng.gen_range(0..3));
+            let mask: String = row
+                .chars()
+                .map(|c| if rng.gen_bool(0.4) { '?' } else { c })
+                .collect();
+            let lengths: Vec<String> = lengths.iter().map(|l| l.to_string()).collect();
+            r += &format!("{} {}\n", mask, lengths.join(","));
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+    #[test]
+    fn test() {
+        assert_eq!(variants("..###..", &[2], &mut HashMap::new()), 0);
+        assert_eq!(variants("..###..", &[3], &mut HashMap::new()), 1);
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day12>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(21)
+        );
+        assert_eq!(
+            solve::<Day12>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(525152)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day12>(input, Part::One).unwrap(),
+            Answer::Number(7599)
+        );
+        assert_eq!(
+            solve::<Day12>(input, Part::Two).unwrap(),
+            Answer::Number(15454556629917)
+        );
+    }
+
+    // Tries every assignment of the unknown springs.
+    fn variants_naive(mask: &str, lengths: &[usize]) -> usize {
+        let unknown: Vec<usize> = mask
+            .char_indices()
+            .filter(|(_, c)| *c == '?')
+            .map(|(ind, _)| ind)
+            .collect();
+        (0..1_u32 << unknown.len())
+            .filter(|bits| {
+                let mut springs: Vec<u8> = mask.bytes().collect();
+                for (bit, &ind) in unknown.iter().enumerate() {
+                    springs[ind] = if bits & (1 << bit) != 0 { b'#' } else { b'.' };
+                }
+                let groups: Vec<usize> = springs
+                    .split(|&c| c == b'.')
+                    .filter(|g| !g.is_empty())
+                    .map(|g| g.len())
+                    .collect();
+                groups == lengths
+            })
+            .count()
+    }
+
+    proptest! {
+        #[test]
+        fn variants_match_naive(
+            mask in "[.#?]{0,14}",
+            lengths in prop::collection::vec(1..5_usize, 0..5),
+        ) {
+            prop_assert_eq!(
+                variants(&mask, &lengths, &mut HashMap::new()),
+                variants_naive(&mask, &lengths)
+            );
+        }
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day12>(0..10, 100).unwrap();
+    }
+}
@@ -0,0 +1,231 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    grid::{Grid, Pos, Transform, View},
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{error, fmt, mem};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedTile(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedTile(s) => write!(f, "unexpected tile {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn count_smudges_in_row_mirrored_at(map: &View<char>, r: usize, c: usize) -> usize {
+    let mut lhs = c - 1;
+    let mut rhs = c;
+    let mut result = 0;
+    loop {
+        if map[Pos::new(r, lhs)] != map[Pos::new(r, rhs)] {
+            result += 1;
+        }
+        if lhs == 0 || rhs == map.width() - 1 {
+            break;
+        }
+        lhs -= 1;
+        rhs += 1;
+    }
+    result
+}
+
+fn count_smudges_for_map_mirrored_at(map: &View<char>, c: usize) -> usize {
+    (0..map.height())
+        .map(|r| count_smudges_in_row_mirrored_at(map, r, c))
+        .sum()
+}
+
+fn count_mirrors(map: &View<char>, smudges: usize) -> usize {
+    (1..map.width())
+        .filter(|c| count_smudges_for_map_mirrored_at(map, *c) == smudges)
+        .sum()
+}
+
+fn summarize(maps: &[Grid<char>], smudges: usize) -> usize {
+    maps.iter()
+        .map(|map| {
+            count_mirrors(&map.view(Transform::Identity), smudges)
+                + count_mirrors(&map.view(Transform::Transpose), smudges) * 100
+        })
+        .sum()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<Grid<char>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut maps = vec![];
+        let mut rows = vec![];
+        for line in Token::lines(input) {
+            if !line.is_empty() {
+                rows.push(
+                    line.chars()
+                        .map(|t| match t.char() {
+                            c @ ('.' | '#') => Ok(c),
+                            _ => Err(ParseError::UnexpectedTile(t.span())),
+                        })
+                        .collect::<Result<Vec<char>, _>>()?,
+                );
+            } else if !rows.is_empty() {
+                maps.push(Grid::from_rows(mem::take(&mut rows))?);
+            }
+        }
+        if !rows.is_empty() {
+            maps.push(Grid::from_rows(rows)?);
+        }
+        Ok(maps)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(summarize(input, 0).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(summarize(input, 1).into())
+    }
+}
+
+// Reflects `ind` into the lower half of the `size` lines mirrored at
+// `mirror`, the ones outside the reflection stay where they are.
+fn fold(ind: usize, mirror: usize, size: usize) -> usize {
+    let reach = mirror.min(size - mirror);
+    if ind >= mirror && ind < mirror + reach {
+        2 * mirror - 1 - ind
+    } else {
+        ind
+    }
+}
+
+// A pattern with a perfect reflection between rows and a reflection with
+// one smudge between columns, or `None` if there are other reflections with
+// at most one smudge by chance.
+fn pattern<R: Rng>(rng: &mut R) -> Option<Grid<char>> {
+    let (height, width) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+    let row_mirror = rng.gen_range(1..height);
+    let col_mirror = rng.gen_range(1..width);
+    let reach = row_mirror.min(height - row_mirror);
+    if 2 * reach == height {
+        return None;
+    }
+    let mut map = Grid::new(width, height, '.');
+    let base: Vec<bool> = (0..width * height).map(|_| rng.gen_bool(0.5)).collect();
+    for pos in map.positions() {
+        let r = fold(pos.r, row_mirror, height);
+        let c = fold(pos.c, col_mirror, width);
+        if base[r * width + c] {
+            map[pos] = '#';
+        }
+    }
+    let r = loop {
+        let r = rng.gen_range(0..height);
+        if r < row_mirror - reach || r >= row_mirror + reach {
+            break r;
+        }
+    };
+    let col_reach = col_mirror.min(width - col_mirror);
+    let c = rng.gen_range(col_mirror - col_reach..col_mirror + col_reach);
+    let smudge = Pos::new(r, c);
+    map[smudge] = if map[smudge] == '#' { '.' } else { '#' };
+
+    let mut smudges = vec![];
+    for transform in [Transform::Identity, Transform::Transpose] {
+        let view = map.view(transform);
+        smudges.extend((1..view.width()).map(|c| count_smudges_for_map_mirrored_at(&view, c)));
+    }
+    let count = |n| smudges.iter().filter(|s| **s == n).count();
+    if count(0) != 1 || count(1) != 1 {
+        return None;
+    }
+    if rng.gen_bool(0.5) {
+        let view = map.view(Transform::Transpose);
+        map = Grid::from_rows(
+            (0..view.height())
+                .map(|r| (0..view.width()).map(|c| view[Pos::new(r, c)]).collect())
+                .collect(),
+        )
+        .ok()?;
+    }
+    Some(map)
+}
+
+impl Generate for Day13 {
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let maps: Vec<String> = (0..size.max(1))
+            .map(|_| loop {
+                if let Some(map) = pattern(rng) {
+                    break map.to_string();
+                }
+            })
+            .collect();
+        maps.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day13>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(405)
+        );
+        assert_eq!(
+            solve::<Day13>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(400)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day13>(input, Part::One).unwrap(),
+            Answer::Number(27664)
+        );
+        assert_eq!(
+            solve::<Day13>(input, Part::Two).unwrap(),
+            Answer::Number(33991)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day13>(0..10, 50).unwrap();
+    }
+}
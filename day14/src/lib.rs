@@ -0,0 +1,231 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    grid::{Grid, Pos, Transform},
+    parse::Span,
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{collections::HashMap, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedTile(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedTile(s) => write!(f, "unexpected tile {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+// Views putting each of the tilting directions up: north, west, south, east.
+const TILTS: [Transform; 4] = [
+    Transform::Identity,
+    Transform::Transpose,
+    Transform::FlipVertical,
+    Transform::RotateCcw,
+];
+
+struct Platform<'a> {
+    rotation: i32,
+    content: &'a mut Grid<char>,
+    states: HashMap<String, usize>,
+    period: Option<(usize, usize)>,
+}
+
+impl<'a> Platform<'a> {
+    fn new(content: &'a mut Grid<char>) -> Self {
+        Self {
+            rotation: -1,
+            content,
+            states: HashMap::new(),
+            period: None,
+        }
+    }
+
+    fn rotate(&mut self) -> Option<(usize, usize)> {
+        self.rotation += 1;
+        self.roll();
+        if self.period.is_none() && self.rotation % 4 == 0 {
+            let v = self.as_str();
+            if let Some(observed) = self.states.get(&v) {
+                self.period = Some((*observed / 4, (self.rotation as usize - *observed) / 4));
+            } else {
+                self.states.insert(v, self.rotation as usize);
+            }
+        }
+        self.period
+    }
+
+    fn tilt(&self) -> Transform {
+        TILTS[self.rotation as usize % 4]
+    }
+
+    fn rows(&self) -> usize {
+        self.content.view(self.tilt()).height()
+    }
+
+    fn cols(&self) -> usize {
+        self.content.view(self.tilt()).width()
+    }
+
+    fn at(&mut self, r: usize, c: usize) -> &mut char {
+        let tilt = self.tilt();
+        let pos = tilt.source(Pos::new(r, c), self.content.height(), self.content.width());
+        &mut self.content[pos]
+    }
+
+    fn roll(&mut self) {
+        for c in 0..self.cols() {
+            let mut t = 0;
+            let mut s = 1;
+            while s != self.rows() {
+                match (*self.at(t, c), *self.at(s, c)) {
+                    ('.', 'O') => {
+                        *self.at(t, c) = 'O';
+                        *self.at(s, c) = '.';
+                        t += 1;
+                        s += 1;
+                    }
+                    (_, 'O') => {
+                        s += 1;
+                        t = s;
+                    }
+                    ('.', '.') => {
+                        s += 1;
+                    }
+                    (_, '.') => {
+                        t = s;
+                        s += 1;
+                    }
+                    (_, '#') => {
+                        s += 1;
+                        t = s;
+                    }
+                    _ => {
+                        panic!();
+                    }
+                }
+            }
+        }
+    }
+
+    fn load(&self) -> usize {
+        self.content
+            .rows()
+            .enumerate()
+            .map(|(ind, r)| (self.content.height() - ind) * r.iter().filter(|c| **c == 'O').count())
+            .sum()
+    }
+
+    fn as_str(&self) -> String {
+        self.content.to_string()
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse(input, |t| match t.char() {
+            c @ ('O' | '#' | '.') => Ok(c),
+            _ => Err(ParseError::UnexpectedTile(t.span()).into()),
+        })?;
+        Ok(map)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        let mut content = input.clone();
+        let mut platform = Platform::new(&mut content);
+        platform.rotate();
+        Ok(platform.load().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let mut content = input.clone();
+        let mut platform = Platform::new(&mut content);
+        loop {
+            if let Some((start, period)) = platform.rotate() {
+                let remain = (1000000000 - start) % period;
+                for _ in 0..4 * (period + remain) - 1 {
+                    platform.rotate();
+                }
+                break;
+            }
+        }
+        Ok(platform.load().into())
+    }
+}
+
+impl Generate for Day14 {
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let side = size.max(1);
+        let mut map = Grid::new(side, side, '.');
+        for pos in map.positions() {
+            map[pos] = match rng.gen_range(0..10) {
+                0..=1 => 'O',
+                2 => '#',
+                _ => '.',
+            };
+        }
+        map.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day14>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(136)
+        );
+        assert_eq!(
+            solve::<Day14>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(64)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day14>(input, Part::One).unwrap(),
+            Answer::Number(108826)
+        );
+        assert_eq!(
+            solve::<Day14>(input, Part::Two).unwrap(),
+            Answer::Number(99291)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day14>(0..10, 30).unwrap();
+    }
+}
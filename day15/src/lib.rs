@@ -0,0 +1,272 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    error, fmt,
+    rc::{Rc, Weak},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidStep(Span),
+    InvalidFocus(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidStep(s) => {
+                write!(f, "expected `<label>=<focus>` or `<label>-`, got {}", s)
+            }
+            ParseError::InvalidFocus(s) => write!(f, "invalid focal length {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+struct LenseNode {
+    focus: usize,
+    next: Option<Rc<RefCell<LenseNode>>>,
+    prev: Option<Weak<RefCell<LenseNode>>>,
+}
+
+struct LenseList {
+    head: Rc<RefCell<LenseNode>>,
+    tail: Rc<RefCell<LenseNode>>,
+}
+
+impl LenseList {
+    fn new() -> Self {
+        let head = Rc::new(RefCell::new(LenseNode {
+            focus: 0,
+            next: None,
+            prev: None,
+        }));
+        let tail = Rc::new(RefCell::new(LenseNode {
+            focus: 0,
+            next: None,
+            prev: Some(Rc::downgrade(&head)),
+        }));
+        head.borrow_mut().next = Some(tail.clone());
+        Self {
+            head: head.clone(),
+            tail: tail.clone(),
+        }
+    }
+
+    fn add(&mut self, focus: usize) -> Rc<RefCell<LenseNode>> {
+        let node = Rc::new(RefCell::new(LenseNode {
+            focus,
+            next: Some(self.tail.clone()),
+            prev: self.tail.borrow().prev.clone(),
+        }));
+        if let Some(prev) = self.tail.borrow().prev.clone() {
+            prev.upgrade().unwrap().borrow_mut().next = Some(node.clone());
+        }
+        self.tail.borrow_mut().prev = Some(Rc::downgrade(&node));
+        node
+    }
+
+    fn remove(&mut self, node: Rc<RefCell<LenseNode>>) {
+        if let (Some(prev), Some(next)) = (node.borrow().prev.clone(), node.borrow().next.clone()) {
+            prev.upgrade().unwrap().borrow_mut().next = Some(next.clone());
+            next.borrow_mut().prev = Some(prev.clone());
+        }
+    }
+
+    fn focusing_power(&self) -> usize {
+        let mut result = 0;
+        let mut ind = 0;
+        let mut current = Some(self.head.clone());
+        while let Some(n) = current {
+            result += n.borrow().focus * ind;
+            ind += 1;
+            current = n.borrow().next.clone();
+        }
+        result
+    }
+}
+
+struct LenseBox {
+    lenses_by_label: HashMap<String, Rc<RefCell<LenseNode>>>,
+    lenses: LenseList,
+}
+
+impl Default for LenseBox {
+    fn default() -> Self {
+        LenseBox::new()
+    }
+}
+
+impl LenseBox {
+    fn new() -> Self {
+        LenseBox {
+            lenses_by_label: HashMap::new(),
+            lenses: LenseList::new(),
+        }
+    }
+
+    fn remove(&mut self, label: &str) {
+        if let Some(n) = self.lenses_by_label.remove(label) {
+            self.lenses.remove(n);
+        }
+    }
+
+    fn set(&mut self, label: &str, focus: usize) {
+        if let Some(n) = self.lenses_by_label.get(label) {
+            n.borrow_mut().focus = focus;
+        } else {
+            self.lenses_by_label
+                .insert(String::from(label), self.lenses.add(focus));
+        }
+    }
+
+    fn focusing_power(&self) -> usize {
+        self.lenses.focusing_power()
+    }
+}
+
+fn hash(s: &str) -> u8 {
+    s.chars()
+        .fold(0, |acc, c| acc.wrapping_add(c as u8).wrapping_mul(17))
+}
+
+pub struct Step {
+    raw: String,
+    label: String,
+    // None for removing the lense
+    focus: Option<usize>,
+}
+
+fn parse_step(step: Token) -> Result<Step, ParseError> {
+    let (label, focus) = if let Some((label, focus)) = step.split_once("=") {
+        (
+            label,
+            Some(focus.parse().map_err(ParseError::InvalidFocus)?),
+        )
+    } else if let Some(label) = step.strip_suffix("-") {
+        (label, None)
+    } else {
+        return Err(ParseError::InvalidStep(step.span()));
+    };
+    Ok(Step {
+        raw: step.text().to_string(),
+        label: label.text().to_string(),
+        focus,
+    })
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Vec<Step>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut steps = vec![];
+        for line in Token::lines(input).filter(|l| !l.is_empty()) {
+            for step in line.split(",") {
+                steps.push(parse_step(step)?);
+            }
+        }
+        Ok(steps)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(input
+            .iter()
+            .map(|s| hash(&s.raw) as usize)
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let mut boxes: Vec<LenseBox> = (0..256).map(|_| LenseBox::default()).collect();
+        for step in input {
+            let bx = &mut boxes[hash(&step.label) as usize];
+            match step.focus {
+                Some(focus) => bx.set(&step.label, focus),
+                None => bx.remove(&step.label),
+            }
+        }
+        Ok(boxes
+            .iter()
+            .enumerate()
+            .map(|(ind, bx)| (ind + 1) * bx.focusing_power())
+            .sum::<usize>()
+            .into())
+    }
+}
+
+impl Generate for Day15 {
+    // Steps on a limited set of labels, so that lenses get replaced and
+    // removed as well as added.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let labels: Vec<String> = (0..size / 4 + 1)
+            .map(|_| {
+                (0..rng.gen_range(2..=6))
+                    .map(|_| rng.gen_range('a'..='z'))
+                    .collect()
+            })
+            .collect();
+        let steps: Vec<String> = (0..size.max(1))
+            .map(|_| {
+                let label = labels.choose(rng).unwrap();
+                if rng.gen_bool(0.7) {
+                    format!("{}={}", label, rng.gen_range(1..=9))
+                } else {
+                    format!("{}-", label)
+                }
+            })
+            .collect();
+        steps.join(",") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day15>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(1320)
+        );
+        assert_eq!(
+            solve::<Day15>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(145)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day15>(input, Part::One).unwrap(),
+            Answer::Number(511215)
+        );
+        assert_eq!(
+            solve::<Day15>(input, Part::Two).unwrap(),
+            Answer::Number(236057)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day15>(0..10, 1000).unwrap();
+    }
+}
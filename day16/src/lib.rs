@@ -0,0 +1,234 @@
+use anyhow::{Context, Result};
+use common::{
+    generate::Generate,
+    grid::{Direction, Grid, Pos},
+    parse::Span,
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{char, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedTile(Span),
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedTile(s) => write!(f, "unexpected tile {}", s),
+            ParseError::Empty => write!(f, "the contraption is empty"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+// Bit of the direction in the mask of the beams that already passed a tile.
+fn mask(dir: Direction) -> u8 {
+    1 << dir as u8
+}
+
+fn reflect(dir: Direction, c: char) -> Vec<Direction> {
+    match (dir, c) {
+        (Direction::Up, '|') => vec![dir],
+        (Direction::Down, '|') => vec![dir],
+        (Direction::Left, '-') => vec![dir],
+        (Direction::Right, '-') => vec![dir],
+
+        (Direction::Up, '-') => vec![Direction::Left, Direction::Right],
+        (Direction::Down, '-') => vec![Direction::Left, Direction::Right],
+        (Direction::Left, '|') => vec![Direction::Up, Direction::Down],
+        (Direction::Right, '|') => vec![Direction::Up, Direction::Down],
+
+        (Direction::Up, '/') => vec![Direction::Right],
+        (Direction::Down, '/') => vec![Direction::Left],
+        (Direction::Left, '/') => vec![Direction::Down],
+        (Direction::Right, '/') => vec![Direction::Up],
+
+        (Direction::Up, '\\') => vec![Direction::Left],
+        (Direction::Down, '\\') => vec![Direction::Right],
+        (Direction::Left, '\\') => vec![Direction::Up],
+        (Direction::Right, '\\') => vec![Direction::Down],
+
+        _ => vec![dir],
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Beam {
+    pos: Pos,
+    dir: Direction,
+}
+
+impl Beam {
+    fn new(r: usize, c: usize, d: Direction) -> Self {
+        Self {
+            pos: Pos::new(r, c),
+            dir: d,
+        }
+    }
+}
+
+struct BeamTracer<'a> {
+    pending: Vec<Beam>,
+    processed: Grid<u8>,
+    field: &'a Grid<char>,
+}
+
+impl<'a> BeamTracer<'a> {
+    fn new(field: &'a Grid<char>, enter: Beam) -> Self {
+        Self {
+            pending: vec![enter],
+            processed: Grid::new(field.width(), field.height(), 0),
+            field,
+        }
+    }
+
+    fn trace_beam(&mut self, beam: Beam) {
+        self.processed[beam.pos] |= mask(beam.dir);
+        let reflected = reflect(beam.dir, self.field[beam.pos]);
+        for dir in reflected {
+            if let Some(pos) = self.field.step(beam.pos, dir) {
+                let nb = Beam { pos, dir };
+                if self.processed[nb.pos] & mask(nb.dir) != mask(nb.dir) {
+                    self.pending.push(nb);
+                }
+            }
+        }
+    }
+
+    fn trace(&mut self) {
+        while let Some(beam) = self.pending.pop() {
+            self.trace_beam(beam);
+        }
+    }
+
+    fn count_energized(&mut self) -> usize {
+        self.trace();
+        self.processed.iter().filter(|(_, m)| **m != 0).count()
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse(input, |t| match t.char() {
+            c @ ('.' | '|' | '-' | '/' | '\\') => Ok(c),
+            _ => Err(ParseError::UnexpectedTile(t.span()).into()),
+        })?;
+        if map.height() == 0 {
+            return Err(ParseError::Empty.into());
+        }
+        Ok(map)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(BeamTracer::new(input, Beam::new(0, 0, Direction::Right))
+            .count_energized()
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let field = input;
+        let part2 = (0..field.height())
+            .flat_map(|v| {
+                [
+                    Beam::new(0, v, Direction::Down),
+                    Beam::new(field.height() - 1, v, Direction::Up),
+                    Beam::new(v, 0, Direction::Right),
+                    Beam::new(v, field.width() - 1, Direction::Left),
+                ]
+            })
+            .map(|enter| BeamTracer::new(field, enter).count_energized())
+            .max()
+            .context("unreachable - there is a result for each enter")?;
+        Ok(part2.into())
+    }
+}
+
+impl Generate for Day16 {
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let side = size.max(1);
+        let mut map = Grid::new(side, side, '.');
+        for pos in map.positions() {
+            if rng.gen_bool(0.1) {
+                map[pos] = *['|', '-', '/', '\\'].choose(rng).unwrap();
+            }
+        }
+        map.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+.|...\\....
+|.-.\\.....
+.....|-...
+........|.
+..........
+.........\\
+..../.\\\\..
+.-.-/..|..
+.|....-|.\\
+..//.|....
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day16>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(46)
+        );
+        assert_eq!(
+            solve::<Day16>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(51)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |input: &str| {
+            Day16::parse(input)
+                .err()
+                .and_then(|e| e.downcast::<ParseError>().ok())
+        };
+        assert_eq!(err("\n\n"), Some(ParseError::Empty));
+        assert_eq!(
+            err(".|\n.x\n"),
+            Some(ParseError::UnexpectedTile(Span {
+                line: 2,
+                column: 2,
+                token: "x".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day16>(input, Part::One).unwrap(),
+            Answer::Number(7199)
+        );
+        assert_eq!(
+            solve::<Day16>(input, Part::Two).unwrap(),
+            Answer::Number(7438)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day16>(0..10, 30).unwrap();
+    }
+}
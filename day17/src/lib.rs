@@ -0,0 +1,248 @@
+use anyhow::{Context, Result};
+use common::{
+    generate::Generate,
+    grid::{Direction, Grid, Pos},
+    parse::Span,
+    Answer, Solution,
+};
+use priority_queue::PriorityQueue;
+use rand::Rng;
+use std::{collections::HashSet, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidDigit(Span),
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidDigit(s) => write!(f, "heat loss must be a digit, got {}", s),
+            ParseError::Empty => write!(f, "the map is empty"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+struct Position {
+    cell: Pos,
+    // None for the starting position as the crucible can go either way
+    dir: Option<Direction>,
+}
+
+impl Position {
+    fn new(cell: Pos, dir: Option<Direction>) -> Self {
+        Self { cell, dir }
+    }
+
+    fn rotate(&self) -> Vec<Position> {
+        Direction::ALL
+            .into_iter()
+            .filter(|dir| Some(*dir) != self.dir)
+            .filter(|dir| Some(dir.opposite()) != self.dir)
+            .map(|dir| Position::new(self.cell, Some(dir)))
+            .collect()
+    }
+
+    fn advance(&self, dist: i32, delta: usize, field: &Grid<i32>) -> Option<(Position, i32)> {
+        let mut t = *self;
+        let mut d = dist;
+        for _ in 0..delta {
+            t.cell = field.step(t.cell, self.dir?)?;
+            d += field[t.cell];
+        }
+        Some((t, d))
+    }
+}
+
+struct PathFinder<'a> {
+    ranges: Vec<usize>,
+    added: HashSet<Position>,
+    pending: PriorityQueue<Position, i32>,
+    field: &'a Grid<i32>,
+    target: Pos,
+}
+
+impl<'a> PathFinder<'a> {
+    fn new(field: &'a Grid<i32>, ranges: Vec<usize>) -> Self {
+        let mut me = Self {
+            ranges,
+            added: HashSet::new(),
+            pending: PriorityQueue::new(),
+            field,
+            target: Pos::new(field.height() - 1, field.width() - 1),
+        };
+        me.pending.push(Position::new(Pos::new(0, 0), None), 0);
+        me
+    }
+
+    fn discover(&mut self, pos: Position, path: i32) {
+        pos.rotate()
+            .iter()
+            .flat_map(|p| {
+                self.ranges
+                    .iter()
+                    .filter_map(|delta| p.advance(path, *delta, self.field))
+            })
+            .filter(|(p, _)| !self.added.contains(p))
+            .for_each(|(p, dist)| {
+                if let Some(old) = self.pending.get_priority(&p) {
+                    if *old < -dist {
+                        self.pending.change_priority(&p, -dist);
+                    }
+                } else {
+                    self.pending.push(p, -dist);
+                }
+            });
+    }
+
+    fn find(&mut self) -> Option<i32> {
+        let mut best: Option<i32> = None;
+        while let Some((pos, dist)) = self.pending.pop() {
+            if pos.cell == self.target {
+                if let Some(old) = best {
+                    if old > -dist {
+                        best = Some(-dist);
+                    }
+                } else {
+                    best = Some(-dist);
+                }
+            }
+            self.added.insert(pos);
+            self.discover(pos, -dist);
+        }
+        best
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Grid<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse(input, |t| {
+            t.char()
+                .to_digit(10)
+                .map(|d| d as i32)
+                .ok_or_else(|| ParseError::InvalidDigit(t.span()).into())
+        })?;
+        if map.height() == 0 {
+            return Err(ParseError::Empty.into());
+        }
+        Ok(map)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(PathFinder::new(input, vec![1, 2, 3])
+            .find()
+            .context("no path to the factory")?
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(PathFinder::new(input, (4..=10).collect())
+            .find()
+            .context("no path to the factory")?
+            .into())
+    }
+}
+
+impl Generate for Day17 {
+    // The ultra crucible needs at least four blocks in every direction.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let side = size.max(5);
+        let mut map = Grid::new(side, side, 0);
+        for pos in map.positions() {
+            map[pos] = rng.gen_range(1..=9);
+        }
+        map.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    const UNFORTUNATE: &str = "\
+111111111111
+999999999991
+999999999991
+999999999991
+999999999991
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day17>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(102)
+        );
+        assert_eq!(
+            solve::<Day17>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(94)
+        );
+        assert_eq!(
+            solve::<Day17>(UNFORTUNATE, Part::Two).unwrap(),
+            Answer::Number(71)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |input: &str| {
+            Day17::parse(input)
+                .err()
+                .and_then(|e| e.downcast::<ParseError>().ok())
+        };
+        assert_eq!(err(""), Some(ParseError::Empty));
+        assert_eq!(
+            err("12\n3a\n"),
+            Some(ParseError::InvalidDigit(Span {
+                line: 2,
+                column: 2,
+                token: "a".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day17>(input, Part::One).unwrap(),
+            Answer::Number(916)
+        );
+        assert_eq!(
+            solve::<Day17>(input, Part::Two).unwrap(),
+            Answer::Number(1067)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day17>(0..10, 15).unwrap();
+    }
+}
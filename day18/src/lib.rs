@@ -0,0 +1,504 @@
+use anyhow::Result;
+use common::{
+    generate::{random_loop, Generate},
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{collections::BTreeMap, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidDirection(Span),
+    InvalidDistance(Span),
+    InvalidColor(Span),
+    MissingField(Span),
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidDirection(s) => write!(f, "expected U, D, L or R instead of {}", s),
+            ParseError::InvalidDistance(s) => write!(f, "invalid distance {}", s),
+            ParseError::InvalidColor(s) => {
+                write!(f, "expected a color like `(#70c710)`, got {}", s)
+            }
+            ParseError::MissingField(s) => {
+                write!(f, "expected direction, distance and color in {}", s)
+            }
+            ParseError::Empty => write!(f, "the dig plan is empty"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineState {
+    Open,
+    Close,
+    Continue,
+}
+
+#[derive(Debug)]
+struct Instruction {
+    dir: char,
+    dist: usize,
+}
+
+fn delta(c: char) -> (i64, i64) {
+    match c {
+        'U' => (0, -1),
+        'D' => (0, 1),
+        'L' => (-1, 0),
+        _ => (1, 0),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: i64,
+    y: i64,
+    state: LineState,
+}
+
+impl Point {
+    fn open(x: i64, y: i64) -> Self {
+        Self {
+            x,
+            y,
+            state: LineState::Open,
+        }
+    }
+
+    fn close(x: i64, y: i64) -> Self {
+        Self {
+            x,
+            y,
+            state: LineState::Close,
+        }
+    }
+}
+
+struct Solver<'a> {
+    instructions: &'a [Instruction],
+    points: Vec<Point>,
+    pnt: usize,
+    line: BTreeMap<i64, Point>,
+}
+
+impl<'a> Solver<'a> {
+    fn new(insts: &'a [Instruction]) -> Self {
+        Self {
+            instructions: insts,
+            points: vec![],
+            pnt: 0,
+            line: BTreeMap::new(),
+        }
+    }
+
+    fn measure(line: &BTreeMap<i64, Point>) -> i64 {
+        let mut r = 0;
+        let mut inside = false;
+        let mut prev = &Point {
+            x: 0,
+            y: 0,
+            state: LineState::Continue,
+        };
+        let mut open = false;
+        for p in line.values() {
+            match (prev.state, open, inside, p.state) {
+                (_, _, true, LineState::Continue) => {
+                    r += p.x - prev.x;
+                    inside = false;
+                }
+                (_, _, false, LineState::Continue) => {
+                    r += 1;
+                    inside = true;
+                    prev = p;
+                }
+                (_, false, true, LineState::Close) => {
+                    r += p.x - prev.x;
+                    prev = p;
+                    open = true;
+                }
+                (_, false, true, LineState::Open) => {
+                    r += p.x - prev.x;
+                    prev = p;
+                    open = true;
+                }
+                (_, false, false, LineState::Close) => {
+                    r += 1;
+                    prev = p;
+                    open = true;
+                }
+                (_, false, false, LineState::Open) => {
+                    r += 1;
+                    prev = p;
+                    open = true;
+                }
+                (LineState::Open, true, _, LineState::Open) => {
+                    r += p.x - prev.x;
+                    prev = p;
+                    open = false;
+                }
+                (LineState::Close, true, _, LineState::Close) => {
+                    r += p.x - prev.x;
+                    prev = p;
+                    open = false;
+                }
+                (LineState::Close, true, _, LineState::Open) => {
+                    r += p.x - prev.x;
+                    prev = p;
+                    open = false;
+                    inside = !inside;
+                }
+                (LineState::Open, true, _, LineState::Close) => {
+                    r += p.x - prev.x;
+                    prev = p;
+                    open = false;
+                    inside = !inside;
+                }
+                _ => {}
+            }
+        }
+        r
+    }
+
+    fn solve(&mut self) -> i64 {
+        let mut result = 0;
+        self.analyze_path();
+
+        let mut last = self.calculate_next_line().unwrap();
+        result += Self::measure(&self.line);
+
+        self.finalyze_line();
+        let mut per_line = Self::measure(&self.line);
+
+        while let Some(y) = self.calculate_next_line() {
+            result += (y - last - 1) * per_line;
+            result += Self::measure(&self.line);
+
+            self.finalyze_line();
+            per_line = Self::measure(&self.line);
+
+            last = y;
+        }
+        result
+    }
+
+    fn analyze_path(&mut self) {
+        let mut x = 0;
+        let mut y = 0;
+        self.instructions.iter().for_each(|inst| {
+            let d = delta(inst.dir);
+            let nx = x + d.0 * inst.dist as i64;
+            let ny = y + d.1 * inst.dist as i64;
+            if inst.dir == 'U' {
+                self.points.push(Point::open(x, ny));
+                self.points.push(Point::close(x, y));
+            } else if inst.dir == 'D' {
+                self.points.push(Point::open(x, y));
+                self.points.push(Point::close(x, ny));
+            };
+            x = nx;
+            y = ny;
+        });
+        self.points.sort_by_key(|pt| pt.y);
+    }
+
+    fn calculate_next_line(&mut self) -> Option<i64> {
+        if self.pnt == self.points.len() {
+            return None;
+        }
+        let y = self.points[self.pnt].y;
+        while self.pnt < self.points.len() && self.points[self.pnt].y == y {
+            let pt = self.points[self.pnt];
+            self.pnt += 1;
+            self.line.insert(pt.x, pt);
+        }
+        Some(y)
+    }
+
+    fn finalyze_line(&mut self) {
+        let preserved: Vec<Point> = self
+            .line
+            .values()
+            .filter(|v| v.state != LineState::Close)
+            .cloned()
+            .collect();
+        self.line = BTreeMap::new();
+        for p in preserved {
+            self.line.insert(
+                p.x,
+                Point {
+                    x: p.x,
+                    y: p.y,
+                    state: LineState::Continue,
+                },
+            );
+        }
+    }
+}
+
+// Reads the instruction of a line along with the one encoded in its color.
+fn instructions(line: Token) -> Result<(Instruction, Instruction), ParseError> {
+    let fields: Vec<Token> = line.words().collect();
+    let [dir, dist, color] = fields[..] else {
+        return Err(ParseError::MissingField(line.span()));
+    };
+    if !matches!(dir.text(), "U" | "D" | "L" | "R") {
+        return Err(ParseError::InvalidDirection(dir.span()));
+    }
+    let plan = Instruction {
+        dir: dir.char(),
+        dist: dist.parse().map_err(ParseError::InvalidDistance)?,
+    };
+
+    let invalid_color = || ParseError::InvalidColor(color.span());
+    let hex = color
+        .strip_prefix("(#")
+        .and_then(|c| c.strip_suffix(")"))
+        .filter(|c| c.text().len() == 6 && c.text().is_ascii())
+        .ok_or_else(invalid_color)?
+        .text();
+    let dist = usize::from_str_radix(&hex[..5], 16).map_err(|_| invalid_color())?;
+    let dir = match &hex[5..] {
+        "0" => 'R',
+        "1" => 'D',
+        "2" => 'L',
+        "3" => 'U',
+        _ => return Err(invalid_color()),
+    };
+    Ok((plan, Instruction { dir, dist }))
+}
+
+// The instructions to dig along the corners of a closed path.
+fn dig_plan(corners: &[(i64, i64)]) -> Vec<Instruction> {
+    let len = corners.len();
+    (0..len)
+        .map(|ind| {
+            let (x0, y0) = corners[ind];
+            let (x1, y1) = corners[(ind + 1) % len];
+            let (dir, dist) = match (x1 - x0, y1 - y0) {
+                (dx, 0) if dx > 0 => ('R', dx),
+                (dx, 0) => ('L', -dx),
+                (0, dy) if dy > 0 => ('D', dy),
+                (_, dy) => ('U', -dy),
+            };
+            Instruction {
+                dir,
+                dist: dist as usize,
+            }
+        })
+        .collect()
+}
+
+pub struct DigPlan {
+    plan: Vec<Instruction>,
+    decoded: Vec<Instruction>,
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = DigPlan;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut plan = vec![];
+        let mut decoded = vec![];
+        for line in Token::lines(input).filter(|l| !l.is_empty()) {
+            let (inst, color) = instructions(line)?;
+            plan.push(inst);
+            decoded.push(color);
+        }
+        if plan.is_empty() {
+            return Err(ParseError::Empty.into());
+        }
+        Ok(DigPlan { plan, decoded })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(Solver::new(&input.plan).solve().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(Solver::new(&input.decoded).solve().into())
+    }
+}
+
+impl Generate for Day18 {
+    // The colors encode the same lagoon stretched by random factors, which
+    // keeps the trench from touching itself.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let corners = random_loop(rng, size, 10, 10);
+        let longest = corners.iter().flat_map(|&(x, y)| [x, y]).max().unwrap_or(0) + 1;
+        let limit = (1 << 20) / longest;
+        let (sx, sy) = (rng.gen_range(1..=limit), rng.gen_range(1..=limit));
+        let stretched: Vec<(i64, i64)> = corners.iter().map(|&(x, y)| (x * sx, y * sy)).collect();
+        let mut r = String::new();
+        for (inst, color) in dig_plan(&corners).iter().zip(dig_plan(&stretched)) {
+            let code = "RDLU".find(color.dir).unwrap();
+            r += &format!("{} {} (#{:05x}{})\n", inst.dir, inst.dist, color.dist, code);
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        generate::{loop_corners, solve_generated},
+        solve, Part,
+    };
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    const EXAMPLE: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c7c0)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
+
+    // The colour codes encode the same plan, so both parts dig the same lagoon.
+    const ENCODED: &str = "\
+R 6 (#000060)
+D 5 (#000051)
+L 2 (#000022)
+D 2 (#000021)
+R 2 (#000020)
+D 2 (#000021)
+L 5 (#000052)
+U 2 (#000023)
+L 1 (#000012)
+U 2 (#000023)
+R 2 (#000020)
+U 3 (#000033)
+L 2 (#000022)
+U 2 (#000023)
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day18>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(62)
+        );
+        assert_eq!(
+            solve::<Day18>(ENCODED, Part::Two).unwrap(),
+            Answer::Number(62)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |input: &str| {
+            Day18::parse(input)
+                .err()
+                .and_then(|e| e.downcast::<ParseError>().ok())
+        };
+        assert_eq!(
+            err("R 6 (#70c710)\nX 5 (#0dc571)"),
+            Some(ParseError::InvalidDirection(Span {
+                line: 2,
+                column: 1,
+                token: "X".to_string()
+            }))
+        );
+        assert_eq!(
+            err("R 6 (#70c71)"),
+            Some(ParseError::InvalidColor(Span {
+                line: 1,
+                column: 5,
+                token: "(#70c71)".to_string()
+            }))
+        );
+        assert_eq!(err("\n  \n"), Some(ParseError::Empty));
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day18>(input, Part::One).unwrap(),
+            Answer::Number(95356)
+        );
+        assert_eq!(
+            solve::<Day18>(input, Part::Two).unwrap(),
+            Answer::Number(92291468914147)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day18>(0..10, 30).unwrap();
+    }
+
+    // Digs the trench cell by cell and floods the ground around it.
+    fn area_naive(instructions: &[Instruction]) -> i64 {
+        let (mut x, mut y) = (0, 0);
+        let mut trench = HashSet::from([(x, y)]);
+        for inst in instructions {
+            let (dx, dy) = delta(inst.dir);
+            for _ in 0..inst.dist {
+                x += dx;
+                y += dy;
+                trench.insert((x, y));
+            }
+        }
+        let min_x = trench.iter().map(|p| p.0).min().unwrap() - 1;
+        let max_x = trench.iter().map(|p| p.0).max().unwrap() + 1;
+        let min_y = trench.iter().map(|p| p.1).min().unwrap() - 1;
+        let max_y = trench.iter().map(|p| p.1).max().unwrap() + 1;
+        let mut outside = HashSet::from([(min_x, min_y)]);
+        let mut pending = vec![(min_x, min_y)];
+        while let Some((x, y)) = pending.pop() {
+            for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                let next = (x + dx, y + dy);
+                if (min_x..=max_x).contains(&next.0)
+                    && (min_y..=max_y).contains(&next.1)
+                    && !trench.contains(&next)
+                    && outside.insert(next)
+                {
+                    pending.push(next);
+                }
+            }
+        }
+        (max_x - min_x + 1) * (max_y - min_y + 1) - outside.len() as i64
+    }
+
+    // The trench around random columns, see `loop_corners`.
+    fn lagoon() -> impl Strategy<Value = Vec<Instruction>> {
+        (
+            prop::collection::vec((1..4_i64, 0..5_i64, 0..5_i64), 1..7),
+            any::<bool>(),
+            any::<bool>(),
+            any::<usize>(),
+        )
+            .prop_map(|(columns, transpose, reverse, start)| {
+                dig_plan(&loop_corners(&columns, transpose, reverse, start))
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn solver_matches_flood_fill(instructions in lagoon()) {
+            prop_assert_eq!(Solver::new(&instructions).solve(), area_naive(&instructions));
+        }
+    }
+}
@@ -0,0 +1,604 @@
+use anyhow::{bail, Result};
+use common::{
+    budget,
+    generate::{self, Generate},
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnknownAttribute(Span),
+    InvalidComparison(Span),
+    InvalidNumber(Span),
+    InvalidWorkflow(Span),
+    MissingFallback(Span),
+    UnknownWorkflow(Span),
+    MissingStart,
+    InvalidItem(Span),
+    MissingRating(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnknownAttribute(s) => write!(f, "expected x, m, a or s instead of {}", s),
+            ParseError::InvalidComparison(s) => write!(f, "expected < or > instead of {}", s),
+            ParseError::InvalidNumber(s) => write!(f, "invalid number {}", s),
+            ParseError::InvalidWorkflow(s) => write!(f, "expected `name{{rules}}`, got {}", s),
+            ParseError::MissingFallback(s) => {
+                write!(f, "the last rule of {} must be unconditional", s)
+            }
+            ParseError::UnknownWorkflow(s) => write!(f, "workflow {} is not defined", s),
+            ParseError::MissingStart => write!(f, "there is no `in` workflow"),
+            ParseError::InvalidItem(s) => {
+                write!(f, "expected `{{x=..,m=..,a=..,s=..}}`, got {}", s)
+            }
+            ParseError::MissingRating(s) => write!(f, "not all the ratings are given in {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+type Item = Vec<usize>;
+type Group = Vec<(usize, usize)>;
+
+const MAX_RATING: usize = 4000;
+
+struct Condition {
+    ind: usize,
+    value: usize,
+    check: char,
+}
+
+fn attribute(t: Token) -> Result<usize, ParseError> {
+    match t.text() {
+        "x" => Ok(0),
+        "m" => Ok(1),
+        "a" => Ok(2),
+        "s" => Ok(3),
+        _ => Err(ParseError::UnknownAttribute(t.span())),
+    }
+}
+
+impl Condition {
+    fn parse(cond: Token) -> Result<Self, ParseError> {
+        let mut chars = cond.chars();
+        let ind = attribute(chars.next().unwrap_or(cond))?;
+        let check = chars.next().unwrap_or(cond.end());
+        if !matches!(check.char(), '<' | '>') {
+            return Err(ParseError::InvalidComparison(check.span()));
+        }
+        let value = cond
+            .split_once(check.text())
+            .map_or(cond.end(), |(_, v)| v)
+            .parse()
+            .map_err(ParseError::InvalidNumber)?;
+        Ok(Self {
+            ind,
+            value,
+            check: check.char(),
+        })
+    }
+
+    fn pass(&self, item: &Item) -> bool {
+        let actual = item[self.ind];
+        match self.check {
+            '>' => actual > self.value,
+            '<' => actual < self.value,
+            _ => panic!("Unexpected operation"),
+        }
+    }
+
+    fn filter(&self, gr: &Group) -> Option<Group> {
+        let mut lo = gr[self.ind].0;
+        let mut hi = gr[self.ind].1;
+        match self.check {
+            '>' => lo = cmp::max(lo, self.value + 1),
+            '<' => hi = cmp::min(hi, self.value.saturating_sub(1)),
+            _ => panic!("Unexpected operation"),
+        };
+        if hi >= lo {
+            let mut r = gr.clone();
+            r[self.ind] = (lo, hi);
+            return Some(r);
+        }
+        None
+    }
+
+    fn remaining(&self, gr: &Group) -> Option<Group> {
+        let mut lo = gr[self.ind].0;
+        let mut hi = gr[self.ind].1;
+        match self.check {
+            '>' => hi = cmp::min(hi, self.value),
+            '<' => lo = cmp::max(lo, self.value),
+            _ => panic!("Unexpected operation"),
+        };
+        if hi >= lo {
+            let mut r = gr.clone();
+            r[self.ind] = (lo, hi);
+            return Some(r);
+        }
+        None
+    }
+}
+
+struct Rule {
+    cnd: Option<Condition>,
+    dst: String,
+}
+
+impl Rule {
+    fn parse(s: Token) -> Result<Rule, ParseError> {
+        if let Some((cond, dst)) = s.split_once(":") {
+            Ok(Rule {
+                cnd: Some(Condition::parse(cond)?),
+                dst: dst.text().to_string(),
+            })
+        } else {
+            Ok(Rule {
+                cnd: None,
+                dst: s.text().to_string(),
+            })
+        }
+    }
+
+    fn pass(&self, item: &Item) -> Option<String> {
+        if self.cnd.is_none() || self.cnd.as_ref().unwrap().pass(item) {
+            Some(self.dst.clone())
+        } else {
+            None
+        }
+    }
+
+    fn filter(&self, gr: &Group) -> Option<(String, Group)> {
+        if self.cnd.is_none() {
+            return Some((self.dst.clone(), gr.clone()));
+        }
+        if let Some(gr) = self.cnd.as_ref().unwrap().filter(gr) {
+            return Some((self.dst.clone(), gr.clone()));
+        }
+        None
+    }
+
+    fn remaining(&self, gr: &Group) -> Option<Group> {
+        self.cnd.as_ref()?.remaining(gr)
+    }
+}
+
+pub struct Workflow {
+    rules: Vec<Rule>,
+}
+
+impl Workflow {
+    fn parse(s: Token) -> Result<Self, ParseError> {
+        let rules = s
+            .split(",")
+            .map(Rule::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if rules.last().is_some_and(|r| r.cnd.is_some()) {
+            return Err(ParseError::MissingFallback(s.span()));
+        }
+        Ok(Self { rules })
+    }
+
+    fn process(&self, item: &Item) -> String {
+        self.rules
+            .iter()
+            .map(|r| r.pass(item))
+            .find(|dst| dst.is_some())
+            .unwrap()
+            .unwrap()
+    }
+}
+
+fn read_workflows(lines: &[Token]) -> Result<HashMap<String, Workflow>, ParseError> {
+    let mut r: HashMap<String, Workflow> = HashMap::new();
+    let mut targets = vec![];
+    for l in lines {
+        let (name, rules) = l
+            .strip_suffix("}")
+            .and_then(|l| l.split_once("{"))
+            .ok_or_else(|| ParseError::InvalidWorkflow(l.span()))?;
+        r.insert(name.text().to_string(), Workflow::parse(rules)?);
+        targets.extend(
+            rules
+                .split(",")
+                .map(|r| r.split_once(":").map_or(r, |(_, dst)| dst)),
+        );
+    }
+    if let Some(unknown) = targets
+        .iter()
+        .find(|t| !matches!(t.text(), "A" | "R") && !r.contains_key(t.text()))
+    {
+        return Err(ParseError::UnknownWorkflow(unknown.span()));
+    }
+    if !r.contains_key("in") {
+        return Err(ParseError::MissingStart);
+    }
+    Ok(r)
+}
+
+fn read_item(line: Token) -> Result<Item, ParseError> {
+    let ratings = line
+        .strip_prefix("{")
+        .and_then(|s| s.strip_suffix("}"))
+        .ok_or_else(|| ParseError::InvalidItem(line.span()))?;
+    let mut item = vec![0; 4];
+    let mut given = [false; 4];
+    for rating in ratings.split(",") {
+        let (name, value) = rating
+            .split_once("=")
+            .ok_or_else(|| ParseError::InvalidItem(rating.span()))?;
+        let ind = attribute(name)?;
+        item[ind] = value.parse().map_err(ParseError::InvalidNumber)?;
+        given[ind] = true;
+    }
+    if given.contains(&false) {
+        return Err(ParseError::MissingRating(line.span()));
+    }
+    Ok(item)
+}
+
+// Whether the item is accepted. Workflows only look at the item, so it goes
+// around forever once it is back at a workflow.
+fn accepted(wf: &HashMap<String, Workflow>, item: &Item) -> Result<bool> {
+    let mut flow = String::from("in");
+    let mut visited = HashSet::new();
+    loop {
+        match flow.as_str() {
+            "A" => {
+                return Ok(true);
+            }
+            "R" => {
+                return Ok(false);
+            }
+            _ => {
+                budget::tick(|| {
+                    format!(
+                        "item {:?} went through {} workflows and is at {}",
+                        item,
+                        visited.len(),
+                        flow
+                    )
+                })?;
+                if !visited.insert(flow.clone()) {
+                    bail!("item {:?} goes back to workflow {} forever", item, flow);
+                }
+                flow = wf.get(&flow).unwrap().process(item);
+            }
+        }
+    }
+}
+
+// Splits the items with ratings from 1 to `max_rating` into the groups that
+// are accepted together. The items of a group all take the same workflows,
+// along with the ones it was split from, so they go around forever once the
+// group is back at one of them.
+fn bfs(wfs: &HashMap<String, Workflow>, max_rating: usize) -> Result<Vec<Group>> {
+    let mut r = vec![];
+    let mut pending = vec![(String::from("in"), vec![(1, max_rating); 4], vec![])];
+    while let Some((dst, gr, path)) = pending.pop() {
+        budget::tick(|| {
+            format!(
+                "{} groups accepted, {} waiting for a workflow",
+                r.len(),
+                pending.len() + 1
+            )
+        })?;
+        match dst.as_str() {
+            "A" => r.push(gr),
+            "R" => {}
+            _ => {
+                if path.contains(&dst) {
+                    bail!("items {:?} go back to workflow {} forever", gr, dst);
+                }
+                let mut path = path;
+                path.push(dst.clone());
+                let wf = wfs.get(&dst).unwrap();
+                let mut rem = gr;
+                for r in wf.rules.iter() {
+                    if let Some((dst, gr)) = r.filter(&rem) {
+                        pending.push((dst, gr, path.clone()));
+                    }
+                    if let Some(mrem) = r.remaining(&rem) {
+                        rem = mrem;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    Ok(r)
+}
+
+pub struct System {
+    workflows: HashMap<String, Workflow>,
+    items: Vec<Item>,
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = System;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = Token::lines(input);
+        let workflows: Vec<Token> = lines.by_ref().take_while(|l| !l.is_empty()).collect();
+        let workflows = read_workflows(&workflows)?;
+        let items = lines
+            .filter(|l| !l.is_empty())
+            .map(read_item)
+            .collect::<Result<_, _>>()?;
+        Ok(System { workflows, items })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        let mut total = 0;
+        for item in &input.items {
+            if accepted(&input.workflows, item)? {
+                total += item.iter().sum::<usize>();
+            }
+        }
+        Ok(total.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let passed = bfs(&input.workflows, MAX_RATING)?;
+        Ok(passed
+            .iter()
+            .map(|gr| {
+                gr.iter()
+                    .map(|(lo, hi)| hi - lo + 1)
+                    .fold(1_u64, |acc, v| acc * v as u64)
+            })
+            .sum::<u64>()
+            .into())
+    }
+}
+
+impl Generate for Day19 {
+    // Workflows only send parts to the ones listed after them, so every part
+    // ends up accepted or rejected.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let mut names = vec![String::from("in")];
+        names.extend(
+            generate::names(rng, size.max(1), 2)
+                .into_iter()
+                .filter(|name| name != "in")
+                .take(size.max(1) - 1),
+        );
+        let mut r = String::new();
+        for (ind, name) in names.iter().enumerate() {
+            let destination = |rng: &mut R| match rng.gen_range(ind..names.len() + 1) {
+                later if later > ind && later < names.len() => names[later].clone(),
+                _ => String::from(if rng.gen_bool(0.5) { "A" } else { "R" }),
+            };
+            let mut rules: Vec<String> = (0..rng.gen_range(1..=3))
+                .map(|_| {
+                    format!(
+                        "{}{}{}:{}",
+                        ['x', 'm', 'a', 's'][rng.gen_range(0..4)],
+                        if rng.gen_bool(0.5) { '<' } else { '>' },
+                        rng.gen_range(1..=MAX_RATING),
+                        destination(rng)
+                    )
+                })
+                .collect();
+            rules.push(destination(rng));
+            r += &format!("{}{{{}}}\n", name, rules.join(","));
+        }
+        r.push('\n');
+        for _ in 0..size.max(1) {
+            let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=MAX_RATING));
+            r += &format!("{{x={},m={},a={},s={}}}\n", x, m, a, s);
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        budget::{with_budget, Budget, BudgetExceeded},
+        generate::solve_generated,
+        solve, Part,
+    };
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day19>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(19114)
+        );
+        assert_eq!(
+            solve::<Day19>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(167409079868000)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |input: &str| {
+            Day19::parse(input)
+                .err()
+                .and_then(|e| e.downcast::<ParseError>().ok())
+        };
+        assert_eq!(
+            err("in{q>5:A,R}\n\n{x=1,m=2,a=3,s=4}"),
+            Some(ParseError::UnknownAttribute(Span {
+                line: 1,
+                column: 4,
+                token: "q".to_string()
+            }))
+        );
+        assert_eq!(
+            err("in{x>5:A,R}\n\n{x=1,m=2,a=3}"),
+            Some(ParseError::MissingRating(Span {
+                line: 3,
+                column: 1,
+                token: "{x=1,m=2,a=3}".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let input = "in{x>5:A,ab}\nab{m<3:in,R}\n\n{x=1,m=2,a=3,s=4}\n";
+        assert_eq!(
+            solve::<Day19>(input, Part::One)
+                .unwrap_err()
+                .root_cause()
+                .to_string(),
+            "item [1, 2, 3, 4] goes back to workflow in forever"
+        );
+        assert_eq!(
+            solve::<Day19>(input, Part::Two)
+                .unwrap_err()
+                .root_cause()
+                .to_string(),
+            "items [(1, 5), (1, 2), (1, 4000), (1, 4000)] go back to workflow in forever"
+        );
+        let budget = Budget {
+            time: None,
+            steps: Some(1),
+        };
+        for part in Part::ALL {
+            let err = with_budget(budget, || solve::<Day19>(input, part)).unwrap_err();
+            assert!(err.downcast_ref::<BudgetExceeded>().is_some());
+        }
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day19>(input, Part::One).unwrap(),
+            Answer::Number(383682)
+        );
+        assert_eq!(
+            solve::<Day19>(input, Part::Two).unwrap(),
+            Answer::Number(117954800808317)
+        );
+    }
+
+    const SMALL_RATING: usize = 5;
+
+    // (attribute, is less, value, destination) rules and the fallback
+    // destination of every workflow.
+    type RawWorkflow = (Vec<(usize, bool, usize, usize)>, usize);
+
+    // The workflows only send items to the ones after them, so that every
+    // item is either accepted or rejected.
+    fn workflows(raw: &[RawWorkflow]) -> HashMap<String, Workflow> {
+        let name = |ind: usize| match ind {
+            0 => String::from("in"),
+            _ => format!("w{}", ind),
+        };
+        let destination = |from: usize, choice: usize| match choice % (raw.len() - from + 1) {
+            0 => String::from("A"),
+            1 => String::from("R"),
+            later => name(from + later - 1),
+        };
+        raw.iter()
+            .enumerate()
+            .map(|(ind, (rules, fallback))| {
+                let mut rules: Vec<Rule> = rules
+                    .iter()
+                    .map(|&(attr, less, value, dst)| Rule {
+                        cnd: Some(Condition {
+                            ind: attr,
+                            value,
+                            check: if less { '<' } else { '>' },
+                        }),
+                        dst: destination(ind, dst),
+                    })
+                    .collect();
+                rules.push(Rule {
+                    cnd: None,
+                    dst: destination(ind, *fallback),
+                });
+                (name(ind), Workflow { rules })
+            })
+            .collect()
+    }
+
+    // Runs every possible item through the workflows.
+    fn combinations_naive(wfs: &HashMap<String, Workflow>) -> usize {
+        let ratings = 1..=SMALL_RATING;
+        let mut r = 0;
+        for x in ratings.clone() {
+            for m in ratings.clone() {
+                for a in ratings.clone() {
+                    for s in ratings.clone() {
+                        r += usize::from(accepted(wfs, &vec![x, m, a, s]).unwrap());
+                    }
+                }
+            }
+        }
+        r
+    }
+
+    proptest! {
+        #[test]
+        fn groups_match_naive(
+            raw in prop::collection::vec(
+                (
+                    prop::collection::vec(
+                        (0..4_usize, any::<bool>(), 0..=SMALL_RATING + 1, any::<usize>()),
+                        0..4,
+                    ),
+                    any::<usize>(),
+                ),
+                1..5,
+            ),
+        ) {
+            let wfs = workflows(&raw);
+            let groups: usize = bfs(&wfs, SMALL_RATING)
+                .unwrap()
+                .iter()
+                .map(|gr| gr.iter().map(|(lo, hi)| hi - lo + 1).product::<usize>())
+                .sum();
+            prop_assert_eq!(groups, combinations_naive(&wfs));
+        }
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day19>(0..10, 100).unwrap();
+        // more workflows than there are two letter names
+        solve_generated::<Day19>(0..2, 1000).unwrap();
+    }
+}
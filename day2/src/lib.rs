@@ -0,0 +1,223 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{cmp, error, fmt, ops::BitOr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingId(Span),
+    InvalidId(Span),
+    InvalidCount(Span),
+    MissingColor(Span),
+    UnknownColor(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingId(s) => write!(f, "expected `Game <id>:` in {}", s),
+            ParseError::InvalidId(s) => write!(f, "invalid game id {}", s),
+            ParseError::InvalidCount(s) => write!(f, "invalid number of cubes {}", s),
+            ParseError::MissingColor(s) => write!(f, "missing color after {}", s),
+            ParseError::UnknownColor(s) => write!(f, "unexpected color {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Clone, Copy)]
+struct Rgb(i32, i32, i32);
+
+impl Rgb {
+    fn power(self) -> i32 {
+        self.0 * self.1 * self.2
+    }
+}
+
+impl BitOr for Rgb {
+    type Output = Rgb;
+    fn bitor(self, rhs: Self) -> Rgb {
+        Rgb(
+            cmp::max(self.0, rhs.0),
+            cmp::max(self.1, rhs.1),
+            cmp::max(self.2, rhs.2),
+        )
+    }
+}
+
+pub struct Game {
+    id: i32,
+    sets: Vec<Rgb>,
+}
+
+fn rgb(set: Token) -> Result<Rgb, ParseError> {
+    let mut rgb = Rgb(0, 0, 0);
+    for block in set.split(",") {
+        let mut parts = block.words();
+        let count = parts
+            .next()
+            .ok_or_else(|| ParseError::InvalidCount(block.span()))?;
+        let cnt: i32 = count.parse().map_err(ParseError::InvalidCount)?;
+        let color = parts
+            .next()
+            .ok_or_else(|| ParseError::MissingColor(count.span()))?;
+        match color.text() {
+            "red" => rgb.0 = cnt,
+            "green" => rgb.1 = cnt,
+            "blue" => rgb.2 = cnt,
+            _ => return Err(ParseError::UnknownColor(color.span())),
+        }
+    }
+    Ok(rgb)
+}
+
+fn game(line: Token) -> Result<Game, ParseError> {
+    let (title, sets) = line
+        .split_once(":")
+        .ok_or_else(|| ParseError::MissingId(line.span()))?;
+    let id = title
+        .strip_prefix("Game ")
+        .ok_or_else(|| ParseError::MissingId(title.span()))?;
+    Ok(Game {
+        id: id.trim().parse().map_err(ParseError::InvalidId)?,
+        sets: sets.split(";").map(rgb).collect::<Result<_, _>>()?,
+    })
+}
+
+impl Game {
+    fn power_rgb(&self) -> Rgb {
+        self.sets.iter().fold(Rgb(0, 0, 0), |acc, s| acc | *s)
+    }
+
+    fn possible(&self) -> bool {
+        let rgb = self.power_rgb();
+        rgb.0 <= 12 && rgb.1 <= 13 && rgb.2 <= 14
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Token::lines(input)
+            .filter(|l| !l.is_empty())
+            .map(game)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(input
+            .iter()
+            .filter(|g| g.possible())
+            .map(|g| g.id)
+            .sum::<i32>()
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(input
+            .iter()
+            .map(|g| g.power_rgb().power())
+            .sum::<i32>()
+            .into())
+    }
+}
+
+impl Generate for Day2 {
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let mut r = String::new();
+        for id in 1..=size.max(1) {
+            let sets: Vec<String> = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    colors.shuffle(rng);
+                    colors[..rng.gen_range(1..=3)]
+                        .iter()
+                        .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            r += &format!("Game {}: {}\n", id, sets.join("; "));
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day2>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(8)
+        );
+        assert_eq!(
+            solve::<Day2>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(2286)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |input: &str| {
+            Day2::parse(input)
+                .err()
+                .and_then(|e| e.downcast::<ParseError>().ok())
+        };
+        assert_eq!(
+            err("Game 1: 3 blue, 4 purple"),
+            Some(ParseError::UnknownColor(Span {
+                line: 1,
+                column: 19,
+                token: "purple".to_string()
+            }))
+        );
+        assert_eq!(
+            err("Game 1: 3 blue\nGame x: 1 red"),
+            Some(ParseError::InvalidId(Span {
+                line: 2,
+                column: 6,
+                token: "x".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day2>(input, Part::One).unwrap(),
+            Answer::Number(2879)
+        );
+        assert_eq!(
+            solve::<Day2>(input, Part::Two).unwrap(),
+            Answer::Number(65122)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day2>(0..10, 50).unwrap();
+    }
+}
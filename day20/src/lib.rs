@@ -0,0 +1,467 @@
+use anyhow::{anyhow, bail, Result};
+use common::{
+    budget,
+    generate::Generate,
+    numtheory,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    error, fmt,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingArrow(Span),
+    UnknownModule(Span),
+    MissingOutput(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingArrow(s) => write!(f, "expected `<module> -> <outputs>`, got {}", s),
+            ParseError::UnknownModule(s) => write!(
+                f,
+                "expected broadcaster, %flipflop or &conjunction instead of {}",
+                s
+            ),
+            ParseError::MissingOutput(s) => write!(f, "empty output name in {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Signal {
+    Low,
+    High,
+}
+
+#[derive(Debug)]
+struct Pulse {
+    from: String,
+    to: String,
+    signal: Signal,
+}
+
+struct Block {
+    module: Box<dyn Module>,
+    outputs: Vec<String>,
+}
+
+struct Broadcast {}
+
+impl Module for Broadcast {
+    fn on(&mut self, _: &str, signal: Signal) -> Option<Signal> {
+        Some(signal)
+    }
+
+    fn add_connection(&mut self, _: &str) {}
+}
+
+struct Flipflop {
+    on: bool,
+}
+
+impl Module for Flipflop {
+    fn on(&mut self, _: &str, signal: Signal) -> Option<Signal> {
+        if signal == Signal::High {
+            return None;
+        }
+        self.on = !self.on;
+        Some(if self.on { Signal::High } else { Signal::Low })
+    }
+
+    fn add_connection(&mut self, _: &str) {}
+}
+
+struct Conjuction {
+    mem: HashMap<String, Signal>,
+}
+
+impl Module for Conjuction {
+    fn on(&mut self, from: &str, signal: Signal) -> Option<Signal> {
+        self.mem.insert(from.to_string(), signal);
+        Some(if self.mem.values().all(|s| *s == Signal::High) {
+            Signal::Low
+        } else {
+            Signal::High
+        })
+    }
+
+    fn add_connection(&mut self, from: &str) {
+        self.mem.insert(from.to_string(), Signal::Low);
+    }
+}
+
+trait Module {
+    fn on(&mut self, from: &str, signal: Signal) -> Option<Signal>;
+    fn add_connection(&mut self, from: &str);
+}
+
+pub struct Definition {
+    kind: char,
+    name: String,
+    outputs: Vec<String>,
+}
+
+struct Commutator {
+    blocks: HashMap<String, Block>,
+    pending: VecDeque<Pulse>,
+    presses: usize,
+    watched: Option<String>,
+    triggered: Vec<String>,
+}
+
+impl Commutator {
+    fn new(modules: &[Definition]) -> Self {
+        let mut comm = Commutator {
+            presses: 0,
+            watched: None,
+            triggered: vec![],
+            blocks: HashMap::new(),
+            pending: VecDeque::new(),
+        };
+        let mut from: HashMap<String, Vec<String>> = HashMap::new();
+        for def in modules {
+            let block = Block {
+                module: match def.kind {
+                    '&' => Box::new(Conjuction {
+                        mem: HashMap::new(),
+                    }),
+                    '%' => Box::new(Flipflop { on: false }),
+                    _ => Box::new(Broadcast {}),
+                },
+                outputs: def.outputs.clone(),
+            };
+            for o in block.outputs.iter() {
+                from.entry(o.clone()).or_default().push(def.name.clone());
+            }
+            comm.blocks.insert(def.name.clone(), block);
+        }
+
+        for (k, v) in from {
+            for f in v {
+                if let Some(b) = comm.blocks.get_mut(&k) {
+                    b.module.add_connection(&f);
+                }
+            }
+        }
+        comm
+    }
+
+    fn push_button(&mut self) -> (usize, usize) {
+        self.presses += 1;
+        self.pending.push_back(Pulse {
+            from: "button".to_string(),
+            to: "broadcaster".to_string(),
+            signal: Signal::Low,
+        });
+        self.process()
+    }
+
+    fn process(&mut self) -> (usize, usize) {
+        let mut low = 0_usize;
+        let mut high = 0_usize;
+        while let Some(pulse) = self.pending.pop_front() {
+            if pulse.signal == Signal::Low {
+                low += 1;
+            } else {
+                high += 1;
+                if self.watched.as_ref() == Some(&pulse.to) {
+                    self.triggered.push(pulse.from.clone());
+                }
+            }
+            if let Some(block) = self.blocks.get_mut(&pulse.to) {
+                if let Some(s) = block.module.on(&pulse.from, pulse.signal) {
+                    for o in block.outputs.iter() {
+                        self.pending.push_back(Pulse {
+                            from: pulse.to.clone(),
+                            to: o.clone(),
+                            signal: s.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        (low, high)
+    }
+}
+
+/// Gives up looking for the periods of the modules feeding `rx` after this
+/// many button presses.
+const MAX_PRESSES: usize = 1_000_000;
+
+fn definition(line: Token) -> Result<Definition, ParseError> {
+    let (module, dst) = line
+        .split_once("->")
+        .ok_or_else(|| ParseError::MissingArrow(line.span()))?;
+    let module = module.trim();
+    let (kind, name) = if module.text() == "broadcaster" {
+        ('b', module)
+    } else if let Some(name) = module
+        .strip_prefix("%")
+        .or_else(|| module.strip_prefix("&"))
+        .filter(|n| !n.is_empty())
+    {
+        (module.char(), name)
+    } else {
+        return Err(ParseError::UnknownModule(module.span()));
+    };
+    let outputs = dst
+        .split(",")
+        .map(|o| {
+            let o = o.trim();
+            if o.is_empty() {
+                Err(ParseError::MissingOutput(dst.span()))
+            } else {
+                Ok(o.text().to_string())
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Definition {
+        kind,
+        name: name.text().to_string(),
+        outputs,
+    })
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = Vec<Definition>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Token::lines(input)
+            .filter(|l| !l.is_empty())
+            .map(definition)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        let mut comm = Commutator::new(input);
+        let r = (0..1000)
+            .map(|_| comm.push_button())
+            .fold((0_usize, 0_usize), |(a, b), (c, d)| (a + c, b + d));
+        Ok((r.0 * r.1).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        /*
+         * rx gets a low pulse when the conjunction feeding it has seen high
+         * pulses from all its inputs. Each input sends a high pulse on a
+         * periodic schedule, so find the first two presses it does that
+         * and solve the congruences of all the inputs.
+         */
+        let feeder = input
+            .iter()
+            .find(|d| d.outputs.iter().any(|o| o == "rx"))
+            .ok_or_else(|| anyhow!("no module sends pulses to rx"))?;
+        if feeder.kind != '&' {
+            bail!("rx is fed by {} which is not a conjunction", feeder.name);
+        }
+        let sources: Vec<&str> = input
+            .iter()
+            .filter(|d| d.outputs.contains(&feeder.name))
+            .map(|d| d.name.as_str())
+            .collect();
+        if sources.is_empty() {
+            bail!("no module sends pulses to {}", feeder.name);
+        }
+
+        // a third sighting confirms the period given by the first two
+        let mut comm = Commutator::new(input);
+        comm.watched = Some(feeder.name.clone());
+        let mut highs: HashMap<String, Vec<i128>> = HashMap::new();
+        while sources
+            .iter()
+            .any(|s| highs.get(*s).is_none_or(|v| v.len() < 3))
+        {
+            if comm.presses == MAX_PRESSES {
+                bail!(
+                    "inputs of {} are not periodic within {} presses",
+                    feeder.name,
+                    MAX_PRESSES
+                );
+            }
+            budget::tick(|| {
+                let seen = sources
+                    .iter()
+                    .filter(|s| highs.get(**s).is_some_and(|v| v.len() >= 3))
+                    .count();
+                format!(
+                    "pressed {} times, the periods of {} of the {} inputs of {} are known",
+                    comm.presses,
+                    seen,
+                    sources.len(),
+                    feeder.name
+                )
+            })?;
+            comm.push_button();
+            for from in comm.triggered.drain(..) {
+                let presses = highs.entry(from).or_default();
+                if presses.last() != Some(&(comm.presses as i128)) {
+                    presses.push(comm.presses as i128);
+                }
+            }
+        }
+        let mut congruences: Vec<(i128, i128)> = vec![];
+        for source in &sources {
+            let seen = &highs[*source];
+            let period = seen[1] - seen[0];
+            if seen[2] - seen[1] != period {
+                bail!(
+                    "{} sends high pulses to {} at presses {}, {} and {}, which are not periodic",
+                    source,
+                    feeder.name,
+                    seen[0],
+                    seen[1],
+                    seen[2]
+                );
+            }
+            congruences.push((seen[0], period));
+        }
+        let first = congruences.iter().map(|c| c.0).max().unwrap_or(1);
+        Ok(numtheory::crt_at_least(&congruences, first)?.into())
+    }
+}
+
+const COUNTER_BITS: usize = 12;
+
+impl Generate for Day20 {
+    // The broadcaster drives `size` binary counters of flip-flops. The
+    // counter's conjunction hears the bits that are set in its target and
+    // resets the counter when it reaches the target, which makes an inverter
+    // send a high pulse to the conjunction in front of rx. Like in the real
+    // puzzles, every counter has an odd target with the highest bit set.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let mut used = HashSet::from([String::from("rx")]);
+        let mut fresh = |rng: &mut R| loop {
+            let name: String = (0..2).map(|_| rng.gen_range('a'..='z')).collect();
+            if used.insert(name.clone()) {
+                return name;
+            }
+        };
+        let last = fresh(rng);
+        let mut lines = vec![format!("&{} -> rx", last)];
+        let mut starts = vec![];
+        let mut targets = HashSet::new();
+        for _ in 0..size.clamp(1, 8) {
+            let target = loop {
+                let target = rng.gen_range(1 << (COUNTER_BITS - 1)..1 << COUNTER_BITS) | 1;
+                if targets.insert(target) {
+                    break target;
+                }
+            };
+            let bits: Vec<String> = (0..COUNTER_BITS).map(|_| fresh(rng)).collect();
+            let (hub, inverter) = (fresh(rng), fresh(rng));
+            let mut resets = vec![bits[0].clone(), inverter.clone()];
+            for (ind, bit) in bits.iter().enumerate() {
+                let mut outputs = vec![];
+                if let Some(next) = bits.get(ind + 1) {
+                    outputs.push(next.clone());
+                }
+                if target & (1 << ind) != 0 {
+                    outputs.push(hub.clone());
+                } else {
+                    resets.push(bit.clone());
+                }
+                outputs.shuffle(rng);
+                lines.push(format!("%{} -> {}", bit, outputs.join(", ")));
+            }
+            resets.shuffle(rng);
+            lines.push(format!("&{} -> {}", hub, resets.join(", ")));
+            lines.push(format!("&{} -> {}", inverter, last));
+            starts.push(bits[0].clone());
+        }
+        lines.push(format!("broadcaster -> {}", starts.join(", ")));
+        lines.shuffle(rng);
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        budget::{with_budget, Budget, BudgetExceeded},
+        generate::solve_generated,
+        solve, Part,
+    };
+
+    const EXAMPLE1: &str = "\
+broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+";
+
+    const EXAMPLE2: &str = "\
+broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day20>(EXAMPLE1, Part::One).unwrap(),
+            Answer::Number(32000000)
+        );
+        assert_eq!(
+            solve::<Day20>(EXAMPLE2, Part::One).unwrap(),
+            Answer::Number(11687500)
+        );
+    }
+
+    #[test]
+    fn periods() {
+        // the flip-flop sends a high pulse on every other press
+        let input = "broadcaster -> a\n%a -> con\n&con -> rx\n";
+        assert_eq!(solve::<Day20>(input, Part::Two).unwrap(), Answer::Number(1));
+        let budget = Budget {
+            time: None,
+            steps: Some(2),
+        };
+        let err = with_budget(budget, || solve::<Day20>(input, Part::Two)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<BudgetExceeded>().unwrap().progress,
+            "pressed 2 times, the periods of 0 of the 1 inputs of con are known"
+        );
+        let unfed = "broadcaster -> a\n%a -> a\n&con -> rx\n";
+        assert_eq!(
+            solve::<Day20>(unfed, Part::Two)
+                .unwrap_err()
+                .root_cause()
+                .to_string(),
+            "no module sends pulses to con"
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day20>(input, Part::One).unwrap(),
+            Answer::Number(731517480)
+        );
+        assert_eq!(
+            solve::<Day20>(input, Part::Two).unwrap(),
+            Answer::Number(244178746156661)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day20>(0..4, 4).unwrap();
+    }
+}
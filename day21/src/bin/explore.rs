@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use common::Solution;
+use day21::{
+    visualize::{self, Backend, GifAnimation, PngFrames},
+    Day21,
+};
+use std::{fs, path::PathBuf};
+
+/// Draws the plots of the infinite garden reached after some numbers of
+/// steps.
+#[derive(Parser)]
+struct Args {
+    /// Puzzle input
+    #[arg(default_value = "input.txt")]
+    input: PathBuf,
+    /// Steps at which the reached plots are drawn, the first four times the
+    /// frontier reaches the edge of a tile when omitted
+    #[arg(long, value_delimiter = ',')]
+    steps: Vec<usize>,
+    /// Animated GIF to write the frames to
+    #[arg(long)]
+    gif: Option<PathBuf>,
+    /// Directory to write every frame to as a PNG
+    #[arg(long)]
+    png: Option<PathBuf>,
+    /// Shows the frames in a window
+    #[cfg(feature = "sdl")]
+    #[arg(long)]
+    window: bool,
+    /// Width of the frames, wide enough for the last frame when omitted
+    #[arg(long)]
+    width: Option<u16>,
+    /// Height of the frames, high enough for the last frame when omitted
+    #[arg(long)]
+    height: Option<u16>,
+    /// Writes the number of plots reached at every frame to standard error
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    common::output::set_verbosity(args.verbose);
+    let path = args.input.display();
+    let content = fs::read_to_string(&args.input).with_context(|| format!("reading {}", path))?;
+    let map = Day21::parse(&content).with_context(|| format!("parsing {}", path))?;
+    let side = map.height();
+    let steps = match args.steps.is_empty() {
+        true => (0..4).map(|k| k * side + side / 2).collect(),
+        false => args.steps,
+    };
+    let reach = steps.iter().max().copied().unwrap_or(0);
+    let fit = u16::try_from(2 * reach + 3).unwrap_or(u16::MAX);
+    let (width, height) = (args.width.unwrap_or(fit), args.height.unwrap_or(fit));
+
+    let mut backends: Vec<Box<dyn Backend>> = vec![];
+    if let Some(path) = args.gif {
+        backends.push(Box::new(GifAnimation::new(path, width, height)?));
+    }
+    if let Some(dir) = args.png {
+        backends.push(Box::new(PngFrames::new(
+            dir,
+            u32::from(width),
+            u32::from(height),
+        )?));
+    }
+    #[cfg(feature = "sdl")]
+    if args.window {
+        backends.push(Box::new(visualize::SdlWindow::new(
+            u32::from(width),
+            u32::from(height),
+        )?));
+    }
+    if backends.is_empty() {
+        anyhow::bail!("nothing to draw on, choose --gif, --png or --window with the sdl feature");
+    }
+    let reached = visualize::explore(&map, &steps, &mut backends)?;
+    common::debug!("Size: {}", day21::vacant(&map));
+    println!("Plots reached after {} steps: {}", reach, reached);
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use common::grid::{Grid, Pos};
+use std::collections::VecDeque;
+
+/// Shortest distances from S to the plots of a garden, computed once to
+/// tell how many plots can be reached in exactly some number of steps.
+pub struct DistanceMap {
+    distances: Grid<Option<usize>>,
+    start: Pos,
+    reach: usize,
+    // plots at a distance of at most `d` with the parity of `d`
+    within: Vec<u64>,
+}
+
+impl DistanceMap {
+    /// The garden made of the map alone.
+    pub fn finite(map: &Grid<char>) -> Self {
+        Self::bfs(map, 0, 0, usize::MAX)
+    }
+
+    /// The infinite garden tiled with copies of the map, as far as `reach`
+    /// steps from S: asking for more steps panics.
+    pub fn tiled(map: &Grid<char>, reach: usize) -> Self {
+        let copies_r = reach.div_ceil(map.height());
+        let copies_c = reach.div_ceil(map.width());
+        Self::bfs(map, copies_r, copies_c, reach)
+    }
+
+    // The distances over the map surrounded by the given number of copies
+    // of itself on every side.
+    fn bfs(map: &Grid<char>, copies_r: usize, copies_c: usize, reach: usize) -> Self {
+        let (height, width) = (map.height(), map.width());
+        let mut distances = Grid::new(
+            width * (2 * copies_c + 1),
+            height * (2 * copies_r + 1),
+            None,
+        );
+        let start = map.find(|c| *c == 'S').unwrap_or(Pos::new(0, 0));
+        let start = Pos::new(start.r + copies_r * height, start.c + copies_c * width);
+        distances[start] = Some(0);
+        let mut counts: Vec<u64> = vec![1];
+        let mut pending = VecDeque::from([(start, 0)]);
+        while let Some((pos, dist)) = pending.pop_front() {
+            let neighbors: Vec<Pos> = distances.neighbors4(pos).collect();
+            for n in neighbors {
+                if distances[n].is_none() && *map.get_wrapped(n.r as i64, n.c as i64) != '#' {
+                    distances[n] = Some(dist + 1);
+                    if counts.len() == dist + 1 {
+                        counts.push(0);
+                    }
+                    counts[dist + 1] += 1;
+                    pending.push_back((n, dist + 1));
+                }
+            }
+        }
+        let mut within = counts;
+        for d in 2..within.len() {
+            within[d] += within[d - 2];
+        }
+        Self {
+            distances,
+            start,
+            reach,
+            within,
+        }
+    }
+
+    /// The plots reachable in exactly `steps` steps: those at a distance of
+    /// at most `steps` and of the same parity, since every step can be
+    /// undone.
+    pub fn reachable(&self, steps: usize) -> u64 {
+        assert!(
+            steps <= self.reach,
+            "{} steps go beyond the {} covered by the distance map",
+            steps,
+            self.reach
+        );
+        let last = self.within.len() - 1;
+        match steps.min(last) {
+            d if d % 2 == steps % 2 => self.within[d],
+            // the parity of `steps` has no plots at distance `last`
+            d if d > 0 => self.within[d - 1],
+            _ => 0,
+        }
+    }
+
+    /// The plots reachable in exactly `steps` steps, as `(x, y)` relative
+    /// to S.
+    pub fn plots(&self, steps: usize) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let start = self.start;
+        self.distances
+            .iter()
+            .filter(move |(_, d)| d.is_some_and(|d| d <= steps && d % 2 == steps % 2))
+            .map(move |(pos, _)| (pos.c as i32 - start.c as i32, pos.r as i32 - start.r as i32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parity_queries() {
+        let map = Grid::from_rows(vec![
+            "...".chars().collect(),
+            ".S#".chars().collect(),
+            "...".chars().collect(),
+        ])
+        .unwrap();
+        let finite = DistanceMap::finite(&map);
+        assert_eq!(
+            (0..6).map(|s| finite.reachable(s)).collect::<Vec<_>>(),
+            [1, 3, 5, 3, 5, 3]
+        );
+        assert_eq!(finite.plots(1).count(), 3);
+        let tiled = DistanceMap::tiled(&map, 10);
+        assert_eq!(tiled.reachable(1), 3);
+        assert_eq!(tiled.reachable(10) as usize, tiled.plots(10).count());
+    }
+}
@@ -0,0 +1,270 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    grid::{Grid, Pos},
+    parse::Span,
+    Answer, Solution,
+};
+use distance::DistanceMap;
+use rand::Rng;
+use std::{error, fmt};
+
+pub mod distance;
+pub mod visualize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedTile(Span),
+    MissingStart,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedTile(s) => write!(f, "unexpected tile {}", s),
+            ParseError::MissingStart => write!(f, "there is no starting tile S"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Why the shortcut of the second part does not apply to a map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    NotSquare { width: usize, height: usize },
+    StartNotInMiddle(Pos),
+    BlockedLine(Pos),
+    NotQuadratic { expected: u64, found: u64 },
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapeError::NotSquare { width, height } => {
+                write!(f, "the map is {}x{}, not an odd square", width, height)
+            }
+            ShapeError::StartNotInMiddle(pos) => write!(
+                f,
+                "S is at line {}, column {}, not in the middle of the map",
+                pos.r + 1,
+                pos.c + 1
+            ),
+            ShapeError::BlockedLine(pos) => write!(
+                f,
+                "the rock at line {}, column {} blocks the row or column of S",
+                pos.r + 1,
+                pos.c + 1
+            ),
+            ShapeError::NotQuadratic { expected, found } => write!(
+                f,
+                "the reachable plots do not grow quadratically with the tiles: \
+                 expected {} but found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl error::Error for ShapeError {}
+
+fn find_start(map: &Grid<char>) -> Pos {
+    map.find(|c| *c == 'S').unwrap_or(Pos::new(0, 0))
+}
+
+// Checks that the plots reachable from S in the infinite garden grow
+// tile by tile: the map is an odd square with S in the middle and nothing in
+// the way along its row and column.
+fn check_shape(map: &Grid<char>) -> Result<(), ShapeError> {
+    let (width, height) = (map.width(), map.height());
+    if width != height || width % 2 == 0 {
+        return Err(ShapeError::NotSquare { width, height });
+    }
+    let start = find_start(map);
+    if start != Pos::new(height / 2, width / 2) {
+        return Err(ShapeError::StartNotInMiddle(start));
+    }
+    let row = (0..width).map(|c| Pos::new(start.r, c));
+    let column = (0..height).map(|r| Pos::new(r, start.c));
+    match row.chain(column).find(|pos| map[*pos] == '#') {
+        Some(pos) => Err(ShapeError::BlockedLine(pos)),
+        None => Ok(()),
+    }
+}
+
+/// The plots reachable in exactly `steps` steps in the infinite garden.
+/// Walking `n` more tiles away from S, the frontier crosses whole tiles of
+/// alternating parity and cuts the same corners off the tiles at its edge, so
+/// the count is a quadratic polynomial in `n`: it is fitted on three small
+/// numbers of tiles, checked on a fourth one and extrapolated.
+fn infinite_garden(map: &Grid<char>, steps: usize) -> Result<u64> {
+    check_shape(map)?;
+    let side = map.height();
+    let (rest, tiles) = (steps % side, steps / side);
+    let distances = DistanceMap::tiled(map, rest + 4 * side);
+    if tiles <= 4 {
+        return Ok(distances.reachable(steps));
+    }
+    let samples: Vec<u64> = (1..=4)
+        .map(|n| distances.reachable(rest + n * side))
+        .collect();
+    // Newton's forward differences from one tile
+    let (f1, f2, f3) = (samples[0] as i128, samples[1] as i128, samples[2] as i128);
+    let (d1, d2) = (f2 - f1, f3 - 2 * f2 + f1);
+    let at = |m: i128| f1 + m * d1 + m * (m - 1) / 2 * d2;
+    let expected = at(3);
+    if expected != samples[3] as i128 {
+        return Err(ShapeError::NotQuadratic {
+            expected: expected.max(0) as u64,
+            found: samples[3],
+        }
+        .into());
+    }
+    Ok(at(tiles as i128 - 1) as u64)
+}
+
+pub fn vacant(map: &Grid<char>) -> usize {
+    map.iter().filter(|(_, c)| **c != '#').count()
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse(input, |t| match t.char() {
+            c @ ('.' | '#' | 'S') => Ok(c),
+            _ => Err(ParseError::UnexpectedTile(t.span()).into()),
+        })?;
+        if map.find(|c| *c == 'S').is_none() {
+            return Err(ParseError::MissingStart.into());
+        }
+        Ok(map)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(DistanceMap::finite(input).reachable(64).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(infinite_garden(input, 26501365)?.into())
+    }
+}
+
+impl Generate for Day21 {
+    // Like the real puzzles: an odd square with S in the middle, and the
+    // middle row, the middle column and the edges free of rocks. Plots that
+    // cannot be reached get filled with rocks.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let side = size.max(5) | 1;
+        let middle = side / 2;
+        let mut map = Grid::new(side, side, '.');
+        for pos in map.positions() {
+            let clear = pos.r == middle
+                || pos.c == middle
+                || pos.r == 0
+                || pos.c == 0
+                || pos.r == side - 1
+                || pos.c == side - 1;
+            if !clear && rng.gen_bool(0.15) {
+                map[pos] = '#';
+            }
+        }
+        let start = Pos::new(middle, middle);
+        let mut reached = Grid::new(side, side, false);
+        reached[start] = true;
+        let mut pending = vec![start];
+        while let Some(pos) = pending.pop() {
+            for n in map.neighbors4(pos) {
+                if map[n] == '.' && !reached[n] {
+                    reached[n] = true;
+                    pending.push(n);
+                }
+            }
+        }
+        for pos in map.positions() {
+            if !reached[pos] {
+                map[pos] = '#';
+            }
+        }
+        map[start] = 'S';
+        map.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    #[test]
+    fn example() {
+        let map = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(DistanceMap::finite(&map).reachable(6), 16);
+    }
+
+    #[test]
+    fn shape_errors() {
+        let shape = |input: &str| {
+            infinite_garden(&Day21::parse(input).unwrap(), 26501365)
+                .unwrap_err()
+                .downcast::<ShapeError>()
+                .unwrap()
+        };
+        assert_eq!(shape(EXAMPLE), ShapeError::BlockedLine(Pos::new(5, 1)));
+        assert_eq!(
+            shape("...\n.S.\n...\n...\n"),
+            ShapeError::NotSquare {
+                width: 3,
+                height: 4
+            }
+        );
+        assert_eq!(
+            shape("S..\n...\n...\n"),
+            ShapeError::StartNotInMiddle(Pos::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn small_step_counts() {
+        // without rocks the plots of the right parity fill a diamond
+        let map = Day21::parse(".....\n.....\n..S..\n.....\n.....\n").unwrap();
+        assert_eq!(infinite_garden(&map, 6).unwrap(), 49);
+        assert_eq!(infinite_garden(&map, 100).unwrap(), 101 * 101);
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day21>(input, Part::One).unwrap(),
+            Answer::Number(3615)
+        );
+        assert_eq!(
+            solve::<Day21>(input, Part::Two).unwrap(),
+            Answer::Number(602259568764234)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day21>(0..10, 31).unwrap();
+    }
+}
@@ -0,0 +1,281 @@
+use crate::distance::DistanceMap;
+use anyhow::{Context, Result};
+use common::grid::{Grid, Pos};
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io::BufWriter,
+    path::PathBuf,
+};
+
+/// How a reached plot is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plot {
+    /// In the diamond around the middle of its tile.
+    Inside,
+    /// In a corner of its tile.
+    Outside,
+}
+
+/// The colours of the background, `Plot::Inside` and `Plot::Outside`.
+pub const PALETTE: [[u8; 3]; 3] = [[0, 0, 0], [255, 0, 255], [100, 100, 100]];
+
+fn color_index(plot: Plot) -> u8 {
+    match plot {
+        Plot::Inside => 1,
+        Plot::Outside => 2,
+    }
+}
+
+/// Something that shows the frontier of the infinite garden.
+pub trait Backend {
+    /// Shows the plots reached after `step` steps, with coordinates
+    /// `(x, y)` relative to the starting position.
+    fn draw(&mut self, step: usize, plots: &[((i32, i32), Plot)]) -> Result<()>;
+
+    /// Called once after the last frame.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Backend for Vec<Box<dyn Backend>> {
+    fn draw(&mut self, step: usize, plots: &[((i32, i32), Plot)]) -> Result<()> {
+        self.iter_mut().try_for_each(|b| b.draw(step, plots))
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.iter_mut().try_for_each(|b| b.finish())
+    }
+}
+
+/// Draws the plots of the infinite garden reached at each of the `frames`
+/// steps, returns how many plots are reached at the last one. The frames at
+/// `k * side + side / 2` steps are when the frontier reaches the edge of a
+/// tile, which shows why the second part grows quadratically.
+pub fn explore(map: &Grid<char>, frames: &[usize], backend: &mut dyn Backend) -> Result<u64> {
+    let last = frames.iter().copied().max().unwrap_or(0);
+    let distances = DistanceMap::tiled(map, last);
+    let side = map.height() as i32;
+    let half = side / 2;
+    let start = map.find(|c| *c == 'S').unwrap_or(Pos::new(0, 0));
+    let (sx, sy) = (start.c as i32, start.r as i32);
+    for &step in frames {
+        let plots: Vec<((i32, i32), Plot)> = distances
+            .plots(step)
+            .map(|(x, y)| {
+                let (tx, ty) = ((sx + x).rem_euclid(side), (sy + y).rem_euclid(side));
+                let plot = if (tx - half).abs() + (ty - half).abs() <= half {
+                    Plot::Inside
+                } else {
+                    Plot::Outside
+                };
+                ((x, y), plot)
+            })
+            .collect();
+        common::debug!(
+            "Steps: {} Opened: {} Inside: {}",
+            step,
+            plots.len(),
+            plots.iter().filter(|(_, p)| *p == Plot::Inside).count()
+        );
+        backend.draw(step, &plots)?;
+    }
+    backend.finish()?;
+    Ok(distances.reachable(last))
+}
+
+/// Palette indices of an image `width` by `height` centred on the start.
+fn rasterize(plots: &[((i32, i32), Plot)], width: u32, height: u32) -> Vec<u8> {
+    let mut pixels = vec![0; width as usize * height as usize];
+    for &((x, y), plot) in plots {
+        let (px, py) = (x + width as i32 / 2, y + height as i32 / 2);
+        if (0..width as i32).contains(&px) && (0..height as i32).contains(&py) {
+            pixels[py as usize * width as usize + px as usize] = color_index(plot);
+        }
+    }
+    pixels
+}
+
+/// Writes every frame as `step-NNNNN.png` into a directory.
+pub struct PngFrames {
+    dir: PathBuf,
+    width: u32,
+    height: u32,
+}
+
+impl PngFrames {
+    pub fn new(dir: PathBuf, width: u32, height: u32) -> Result<Self> {
+        fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+        Ok(Self { dir, width, height })
+    }
+}
+
+impl Backend for PngFrames {
+    fn draw(&mut self, step: usize, plots: &[((i32, i32), Plot)]) -> Result<()> {
+        let path = self.dir.join(format!("step-{:05}.png", step));
+        let file = File::create(&path).with_context(|| format!("creating {}", path.display()))?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), self.width, self.height);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(PALETTE.concat());
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&rasterize(plots, self.width, self.height))?;
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Writes the frames as an animated GIF, half a second each.
+pub struct GifAnimation {
+    encoder: Option<gif::Encoder<BufWriter<File>>>,
+    width: u16,
+    height: u16,
+}
+
+impl GifAnimation {
+    pub fn new(path: PathBuf, width: u16, height: u16) -> Result<Self> {
+        let file = File::create(&path).with_context(|| format!("creating {}", path.display()))?;
+        let mut encoder =
+            gif::Encoder::new(BufWriter::new(file), width, height, &PALETTE.concat())?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        Ok(Self {
+            encoder: Some(encoder),
+            width,
+            height,
+        })
+    }
+}
+
+impl Backend for GifAnimation {
+    fn draw(&mut self, _: usize, plots: &[((i32, i32), Plot)]) -> Result<()> {
+        let encoder = self
+            .encoder
+            .as_mut()
+            .context("the animation is already finished")?;
+        let frame = gif::Frame {
+            width: self.width,
+            height: self.height,
+            delay: 50,
+            buffer: Cow::Owned(rasterize(
+                plots,
+                u32::from(self.width),
+                u32::from(self.height),
+            )),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if let Some(encoder) = self.encoder.take() {
+            encoder.into_inner()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sdl")]
+pub use window::SdlWindow;
+
+#[cfg(feature = "sdl")]
+mod window {
+    use super::{Backend, Plot, PALETTE};
+    use anyhow::{anyhow, Result};
+    use sdl2::{pixels::Color, rect::Point, render::Canvas, video::Window};
+    use std::time::Duration;
+
+    /// Shows the frames one after the other in a window.
+    pub struct SdlWindow {
+        canvas: Canvas<Window>,
+    }
+
+    impl SdlWindow {
+        pub fn new(width: u32, height: u32) -> Result<Self> {
+            let sdl_context = sdl2::init().map_err(|e| anyhow!(e))?;
+            let video_subsystem = sdl_context.video().map_err(|e| anyhow!(e))?;
+            let window = video_subsystem
+                .window("explore", width, height)
+                .position_centered()
+                .build()?;
+            Ok(Self {
+                canvas: window.into_canvas().build()?,
+            })
+        }
+    }
+
+    fn color(index: usize) -> Color {
+        let [r, g, b] = PALETTE[index];
+        Color::RGB(r, g, b)
+    }
+
+    impl Backend for SdlWindow {
+        fn draw(&mut self, _: usize, plots: &[((i32, i32), Plot)]) -> Result<()> {
+            let (width, height) = self.canvas.output_size().map_err(|e| anyhow!(e))?;
+            self.canvas.set_draw_color(color(0));
+            self.canvas.clear();
+            for &((x, y), plot) in plots {
+                self.canvas
+                    .set_draw_color(color(usize::from(super::color_index(plot))));
+                self.canvas
+                    .draw_point(Point::new(width as i32 / 2 + x, height as i32 / 2 + y))
+                    .map_err(|e| anyhow!(e))?;
+            }
+            self.canvas.present();
+            std::thread::sleep(Duration::from_millis(500));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day21;
+    use common::Solution;
+
+    const EXAMPLE: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    struct Counts(Vec<(usize, usize)>);
+
+    impl Backend for Counts {
+        fn draw(&mut self, step: usize, plots: &[((i32, i32), Plot)]) -> Result<()> {
+            self.0.push((step, plots.len()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn frames() {
+        let map = Day21::parse(EXAMPLE).unwrap();
+        let mut counts = Counts(vec![]);
+        let reached = explore(&map, &[6, 10, 50], &mut counts).unwrap();
+        assert_eq!(counts.0, [(6, 16), (10, 50), (50, 1594)]);
+        assert_eq!(reached, 1594);
+    }
+
+    #[test]
+    fn animation() {
+        let map = Day21::parse(EXAMPLE).unwrap();
+        let path = std::env::temp_dir().join(format!("day21-{}.gif", std::process::id()));
+        let mut gif = GifAnimation::new(path.clone(), 41, 41).unwrap();
+        explore(&map, &[5, 10, 15], &mut gif).unwrap();
+        let content = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(content.starts_with(b"GIF89a"));
+        assert_eq!(content.last(), Some(&b';'));
+    }
+}
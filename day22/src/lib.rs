@@ -0,0 +1,322 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{collections::HashSet, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingTilde(Span),
+    InvalidPosition(Span),
+    InvalidNumber(Span),
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingTilde(s) => write!(f, "expected `x,y,z~x,y,z`, got {}", s),
+            ParseError::InvalidPosition(s) => write!(f, "expected three coordinates in {}", s),
+            ParseError::InvalidNumber(s) => write!(f, "invalid coordinate {}", s),
+            ParseError::Empty => write!(f, "there are no bricks"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Clone)]
+struct Position {
+    coords: Vec<usize>,
+}
+
+#[derive(Clone)]
+pub struct Brick {
+    a: Position,
+    b: Position,
+    supporters: HashSet<usize>,
+    supporting: HashSet<usize>,
+}
+
+impl Position {
+    fn parse(s: Token) -> Result<Self, ParseError> {
+        let coords: Vec<usize> = s
+            .split(",")
+            .map(|c| c.trim().parse().map_err(ParseError::InvalidNumber))
+            .collect::<Result<_, _>>()?;
+        if coords.len() != 3 {
+            return Err(ParseError::InvalidPosition(s.span()));
+        }
+        Ok(Self { coords })
+    }
+}
+
+impl Brick {
+    fn from_positions(a: Position, b: Position) -> Self {
+        if a.coords.iter().zip(b.coords.iter()).any(|(c1, c2)| c1 < c2) {
+            Self {
+                supporters: HashSet::new(),
+                supporting: HashSet::new(),
+                a,
+                b,
+            }
+        } else {
+            Self {
+                supporters: HashSet::new(),
+                supporting: HashSet::new(),
+                a: b,
+                b: a,
+            }
+        }
+    }
+
+    fn parse(s: Token) -> Result<Self, ParseError> {
+        let (a, b) = s
+            .split_once("~")
+            .ok_or_else(|| ParseError::MissingTilde(s.span()))?;
+        Ok(Self::from_positions(
+            Position::parse(a)?,
+            Position::parse(b)?,
+        ))
+    }
+
+    fn occupied(&self, x: usize, y: usize, z: usize) -> bool {
+        if z < self.a.coords[2] || z > self.b.coords[2] + 1 {
+            return false;
+        }
+
+        if y < self.a.coords[1] || y > self.b.coords[1] {
+            return false;
+        }
+
+        if x < self.a.coords[0] || x > self.b.coords[0] {
+            return false;
+        }
+
+        true
+    }
+
+    fn drop(&mut self) {
+        self.b.coords[2] -= self.a.coords[2] - 1;
+        self.a.coords[2] = 1;
+    }
+
+    fn remove_supporter(&mut self, supporter: usize) -> bool {
+        self.supporters.remove(&supporter);
+        self.supporters.is_empty()
+    }
+
+    fn add_supporter(&mut self, supporter: usize) {
+        self.supporters.insert(supporter);
+    }
+
+    fn add_supported(&mut self, supported: usize) {
+        self.supporting.insert(supported);
+    }
+}
+
+fn count_fallings(brick: usize, bricks: &mut [Brick]) -> usize {
+    let mut fallen: Vec<usize> = vec![];
+    let mut falling: Vec<usize> = vec![brick];
+    while let Some(b) = falling.pop() {
+        fallen.push(b);
+        let supporting = bricks[b].supporting.clone();
+        for s in supporting {
+            if bricks[s].remove_supporter(b) {
+                falling.push(s);
+            }
+        }
+    }
+    // restore the initial state
+    for b in &fallen {
+        let supporting = bricks[*b].supporting.clone();
+        for s in supporting {
+            bricks[s].add_supporter(*b);
+        }
+    }
+    // -1 as fallen contains the brick itself
+    fallen.len() - 1
+}
+
+fn settle(bricks: &[Brick]) -> Vec<Brick> {
+    let mut bricks = bricks.to_vec();
+    bricks.sort_by_key(|br| br.a.coords[2]);
+    bricks[0].drop();
+    for bi in 1..bricks.len() {
+        while bricks[bi].a.coords[2] > 1 {
+            let mut can = true;
+            for x in bricks[bi].a.coords[0]..=bricks[bi].b.coords[0] {
+                for y in bricks[bi].a.coords[1]..=bricks[bi].b.coords[1] {
+                    for j in 0..bi {
+                        if bricks[j].occupied(x, y, bricks[bi].a.coords[2]) {
+                            can = false;
+                            bricks[j].add_supported(bi);
+                            bricks[bi].add_supporter(j);
+                        }
+                    }
+                }
+            }
+            if can {
+                let br = &mut bricks[bi];
+                br.a.coords[2] -= 1;
+                br.b.coords[2] -= 1;
+            } else {
+                break;
+            }
+        }
+    }
+    bricks
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = Vec<Brick>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let bricks: Vec<Brick> = Token::lines(input)
+            .filter(|l| !l.is_empty())
+            .map(Brick::parse)
+            .collect::<Result<_, _>>()?;
+        if bricks.is_empty() {
+            return Err(ParseError::Empty.into());
+        }
+        Ok(bricks)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        let bricks = settle(input);
+        let critical: HashSet<&usize> = bricks
+            .iter()
+            .filter_map(|b| {
+                if b.supporters.len() == 1 {
+                    b.supporters.iter().next()
+                } else {
+                    None
+                }
+            })
+            .collect();
+        Ok((0..bricks.len())
+            .filter(|ind| !critical.contains(ind))
+            .count()
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let mut bricks = settle(input);
+        Ok((0..bricks.len())
+            .map(|b| count_fallings(b, &mut bricks))
+            .sum::<usize>()
+            .into())
+    }
+}
+
+impl Generate for Day22 {
+    // Bricks of up to four cubes in a 10x10 column, placed at random heights
+    // wherever they do not overlap the bricks placed before.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let count = size.max(1);
+        let mut occupied = HashSet::new();
+        let mut r = String::new();
+        let mut placed = 0;
+        while placed < count {
+            let mut a = [rng.gen_range(0..10), rng.gen_range(0..10), 0];
+            a[2] = rng.gen_range(1..=count * 2);
+            let mut b = a;
+            let axis = rng.gen_range(0..3);
+            b[axis] += rng.gen_range(0..4);
+            if axis < 2 && b[axis] > 9 {
+                continue;
+            }
+            let cubes: Vec<[usize; 3]> = (a[0]..=b[0])
+                .flat_map(|x| {
+                    (a[1]..=b[1]).flat_map(move |y| (a[2]..=b[2]).map(move |z| [x, y, z]))
+                })
+                .collect();
+            if cubes.iter().any(|c| occupied.contains(c)) {
+                continue;
+            }
+            occupied.extend(cubes);
+            r += &format!("{},{},{}~{},{},{}\n", a[0], a[1], a[2], b[0], b[1], b[2]);
+            placed += 1;
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day22>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(5)
+        );
+        assert_eq!(
+            solve::<Day22>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(7)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |input: &str| {
+            Day22::parse(input)
+                .err()
+                .and_then(|e| e.downcast::<ParseError>().ok())
+        };
+        assert_eq!(
+            err("1,0,1~1,2"),
+            Some(ParseError::InvalidPosition(Span {
+                line: 1,
+                column: 7,
+                token: "1,2".to_string()
+            }))
+        );
+        assert_eq!(
+            err("1,0,1~1,2,x"),
+            Some(ParseError::InvalidNumber(Span {
+                line: 1,
+                column: 11,
+                token: "x".to_string()
+            }))
+        );
+        assert_eq!(err(""), Some(ParseError::Empty));
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day22>(input, Part::One).unwrap(),
+            Answer::Number(439)
+        );
+        assert_eq!(
+            solve::<Day22>(input, Part::Two).unwrap(),
+            Answer::Number(43056)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day22>(0..10, 100).unwrap();
+    }
+}
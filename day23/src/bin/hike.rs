@@ -0,0 +1,61 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use common::{grid::Pos, Solution};
+use day23::{
+    trails::{self, Trails},
+    Day23,
+};
+use std::{fs, path::PathBuf};
+
+/// Draws the longest hike through the map, between the openings of the top
+/// and bottom rows or any other cells.
+#[derive(Parser)]
+struct Args {
+    /// Puzzle input
+    #[arg(default_value = "input.txt")]
+    input: PathBuf,
+    /// Line and column where the hike starts, counted from 1, the opening
+    /// of the top row when omitted
+    #[arg(long, value_name = "LINE,COLUMN", value_parser = cell)]
+    start: Option<Pos>,
+    /// Line and column where the hike ends, counted from 1, the opening of
+    /// the bottom row when omitted
+    #[arg(long, value_name = "LINE,COLUMN", value_parser = cell)]
+    goal: Option<Pos>,
+    /// Only goes down the slopes, as in the first part
+    #[arg(long)]
+    slopes: bool,
+    /// Searches the hikes on every thread
+    #[arg(long)]
+    parallel: bool,
+}
+
+// A cell given by its line and column counted from 1, like in the errors.
+fn cell(text: &str) -> Result<Pos, String> {
+    let (r, c) = text.split_once(',').ok_or("expected a line and a column")?;
+    let index = |n: &str| match n.trim().parse::<usize>() {
+        Ok(0) => Err(String::from("lines and columns are counted from 1")),
+        Ok(n) => Ok(n - 1),
+        Err(e) => Err(e.to_string()),
+    };
+    Ok(Pos::new(index(r)?, index(c)?))
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let path = args.input.display();
+    let content = fs::read_to_string(&args.input).with_context(|| format!("reading {}", path))?;
+    let map = Day23::parse(&content).with_context(|| format!("parsing {}", path))?;
+    let pos = |cell: Option<Pos>, opening: fn((Pos, Pos)) -> Pos| match cell {
+        Some(cell) => Ok(cell),
+        None => trails::openings(&map).map(opening),
+    };
+    let start = pos(args.start, |(start, _)| start)?;
+    let goal = pos(args.goal, |(_, goal)| goal)?;
+    let route = Trails::between(&map, start, goal, args.slopes)?
+        .longest_route(args.parallel)?
+        .context("no hike between these cells")?;
+    print!("{}", trails::render(&map, &route));
+    println!("{} steps", route.len() - 1);
+    Ok(())
+}
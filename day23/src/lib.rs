@@ -0,0 +1,220 @@
+use anyhow::{Context, Result};
+use common::{
+    generate::Generate,
+    grid::{Grid, Pos},
+    parse::Span,
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{error, fmt};
+use trails::Trails;
+
+pub mod trails;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedTile(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedTile(s) => write!(f, "unexpected tile {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse(input, |t| match t.char() {
+            c @ ('#' | '.' | '^' | '>' | 'v' | '<') => Ok(c),
+            _ => Err(ParseError::UnexpectedTile(t.span()).into()),
+        })?;
+        Ok(map)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(Trails::new(input, true)?
+            .longest(false)?
+            .context("no hike between the openings")?
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(Trails::new(input, false)?
+            .longest(true)?
+            .context("no hike between the openings")?
+            .into())
+    }
+}
+
+const SPACING: usize = 8;
+
+/// The largest lattice the generator writes, like the real puzzles: the
+/// longest hike through a bigger one takes too long to find.
+pub const MAX_SIZE: usize = 6;
+
+// The cells of the corridor from the junction at `from` to the next one to
+// the right or below. Halfway the corridor bends `bend` cells sideways, and
+// its first and last cells are slopes leading away from `from`.
+fn corridor(from: Pos, horizontal: bool, bend: isize) -> Vec<(Pos, char)> {
+    let mut path: Vec<(usize, isize)> = vec![(1, 0), (2, 0)];
+    for across in 0..=bend.abs() {
+        path.push((3, across * bend.signum()));
+    }
+    path.extend([(4, bend), (5, bend)]);
+    for across in (0..bend.abs()).rev() {
+        path.push((5, across * bend.signum()));
+    }
+    path.extend([(6, 0), (7, 0)]);
+    path.into_iter()
+        .map(|(along, across)| {
+            let (pos, slope) = if horizontal {
+                (
+                    Pos::new(from.r.wrapping_add_signed(across), from.c + along),
+                    '>',
+                )
+            } else {
+                (
+                    Pos::new(from.r + along, from.c.wrapping_add_signed(across)),
+                    'v',
+                )
+            };
+            (pos, if along == 1 || along == 7 { slope } else { '.' })
+        })
+        .collect()
+}
+
+// A square lattice of `count` by `count` junctions, like the real puzzles:
+// the slopes around the junctions only lead right and down, and the bends
+// of the corridors never bring them next to each other.
+fn lattice<R: Rng>(rng: &mut R, count: usize) -> String {
+    let side = SPACING * count - 2;
+    let last = 3 + SPACING * (count - 1);
+    let mut map = Grid::new(side, side, '#');
+    for pos in [(0, 1), (1, 1), (2, 1), (3, 1), (3, 2), (last, last + 1)] {
+        map[Pos::new(pos.0, pos.1)] = '.';
+    }
+    map[Pos::new(last + 1, last + 1)] = '.';
+    map[Pos::new(last + 2, last + 1)] = '.';
+    for i in 0..count {
+        for j in 0..count {
+            let junction = Pos::new(3 + SPACING * i, 3 + SPACING * j);
+            map[junction] = '.';
+            if j + 1 < count {
+                let bend = rng.gen_range(-2..=if i + 1 == count { 1 } else { 2 });
+                for (pos, tile) in corridor(junction, true, bend) {
+                    map[pos] = tile;
+                }
+            }
+            if i + 1 < count {
+                let bend = rng.gen_range(-2..=if j + 1 == count { 1 } else { 2 });
+                for (pos, tile) in corridor(junction, false, bend) {
+                    map[pos] = tile;
+                }
+            }
+        }
+    }
+    map.to_string()
+}
+
+impl Generate for Day23 {
+    // The lattice of `size` by `size` junctions, at most `MAX_SIZE`.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        lattice(rng, size.clamp(1, MAX_SIZE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        generate::{generate, solve_generated},
+        solve, Part,
+    };
+
+    const EXAMPLE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day23>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(94)
+        );
+        assert_eq!(
+            solve::<Day23>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(154)
+        );
+    }
+
+    #[test]
+    fn wide() {
+        let width = 300;
+        let map = [
+            format!("#.{}", "#".repeat(width - 2)),
+            format!("#{}#", ".".repeat(width - 2)),
+            format!("{}.#", "#".repeat(width - 2)),
+        ]
+        .join("\n");
+        let answer = Answer::Number(width as i128 - 1);
+        assert_eq!(solve::<Day23>(&map, Part::One).unwrap(), answer);
+        assert_eq!(solve::<Day23>(&map, Part::Two).unwrap(), answer);
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day23>(input, Part::One).unwrap(),
+            Answer::Number(2334)
+        );
+    }
+
+    #[test]
+    fn input_part2() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day23>(input, Part::Two).unwrap(),
+            Answer::Number(6422)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day23>(0..10, 3).unwrap();
+        solve_generated::<Day23>(12..13, 60).unwrap();
+        assert_eq!(generate::<Day23>(12, 60), generate::<Day23>(12, MAX_SIZE));
+    }
+}
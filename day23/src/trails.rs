@@ -0,0 +1,532 @@
+use common::{
+    budget::{self, BudgetExceeded},
+    grid::{Grid, Pos},
+};
+use rayon::prelude::*;
+use std::{
+    collections::HashMap,
+    error, fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HikeError {
+    /// The top or the bottom row has no opening.
+    MissingOpening,
+    /// The hike cannot start or end on a forest cell, or out of the map.
+    Blocked(Pos),
+    /// The search tells junctions apart with the bits of a `u64`.
+    TooManyJunctions(usize),
+    Budget(BudgetExceeded),
+}
+
+impl fmt::Display for HikeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HikeError::MissingOpening => write!(f, "the map is not open at the top and bottom"),
+            HikeError::Blocked(pos) => {
+                write!(
+                    f,
+                    "cannot hike from or to line {}, column {}",
+                    pos.r + 1,
+                    pos.c + 1
+                )
+            }
+            HikeError::TooManyJunctions(count) => {
+                write!(f, "{} junctions are too many to search, at most 64", count)
+            }
+            HikeError::Budget(_) => write!(f, "the hike search was stopped"),
+        }
+    }
+}
+
+impl error::Error for HikeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            HikeError::Budget(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<BudgetExceeded> for HikeError {
+    fn from(err: BudgetExceeded) -> Self {
+        HikeError::Budget(err)
+    }
+}
+
+// Whether the hike can go from `from` to the next cell `to`: the slopes
+// only lead downhill.
+fn downhill(map: &Grid<char>, from: Pos, to: Pos) -> bool {
+    match map[from] {
+        '^' => to.r + 1 == from.r,
+        'v' => to.r == from.r + 1,
+        '<' => to.c + 1 == from.c,
+        '>' => to.c == from.c + 1,
+        _ => true,
+    }
+}
+
+/// The first open cells of the top and the bottom rows, where the hikes
+/// of the puzzle start and end.
+pub fn openings(map: &Grid<char>) -> Result<(Pos, Pos), HikeError> {
+    let opening = |r: usize| {
+        let c = map.row(r).iter().position(|c| *c == '.')?;
+        Some(Pos::new(r, c))
+    };
+    match (opening(0), opening(map.height().saturating_sub(1))) {
+        (Some(start), Some(goal)) => Ok((start, goal)),
+        _ => Err(HikeError::MissingOpening),
+    }
+}
+
+// The first two junctions are where the hike starts and ends.
+const START: usize = 0;
+const GOAL: usize = 1;
+
+// The cells of a trail after the junction it leaves, up to the junction it
+// leads to.
+#[derive(Debug, Clone)]
+struct Trail {
+    to: usize,
+    cells: Vec<Pos>,
+}
+
+/// The map reduced to its junctions and the trails between them: a hike
+/// has no choice to make between two junctions.
+pub struct Trails {
+    junctions: Vec<Pos>,
+    // the trails leaving each junction
+    trails: Vec<Vec<Trail>>,
+}
+
+// A hike as the trail it takes from each junction, by their indices.
+type Steps = Vec<(usize, usize)>;
+
+impl Trails {
+    /// The trails from the opening of the top row to the one of the bottom
+    /// row, only going down the slopes when `slopes` is set.
+    pub fn new(map: &Grid<char>, slopes: bool) -> Result<Self, HikeError> {
+        let (start, goal) = openings(map)?;
+        Self::between(map, start, goal, slopes)
+    }
+
+    /// The trails of hikes from `start` to `goal`, which can be any cells
+    /// that are not forest. They count as junctions, even in the middle of
+    /// a trail.
+    pub fn between(
+        map: &Grid<char>,
+        start: Pos,
+        goal: Pos,
+        slopes: bool,
+    ) -> Result<Self, HikeError> {
+        let open = |pos: Pos| map.get(pos).is_some_and(|c| *c != '#');
+        if let Some(blocked) = [start, goal].into_iter().find(|p| !open(*p)) {
+            return Err(HikeError::Blocked(blocked));
+        }
+        let mut junctions = vec![start, goal];
+        junctions.extend(map.positions().filter(|&pos| {
+            pos != start
+                && pos != goal
+                && open(pos)
+                && map.neighbors4(pos).filter(|&n| open(n)).count() >= 3
+        }));
+        let index: HashMap<Pos, usize> =
+            junctions.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+
+        // hikes never go back to the start nor leave the goal
+        let mut trails = vec![vec![]; junctions.len()];
+        for (from, &junction) in junctions.iter().enumerate().filter(|(j, _)| *j != GOAL) {
+            for first in map.neighbors4(junction).filter(|&n| open(n)) {
+                let mut cells = vec![first];
+                let mut prev = junction;
+                let mut passable = !slopes || downhill(map, junction, first);
+                loop {
+                    let cur = cells[cells.len() - 1];
+                    if let Some(&to) = index.get(&cur) {
+                        if passable && to != from && to != START {
+                            trails[from].push(Trail { to, cells });
+                        }
+                        break;
+                    }
+                    // dead ends lead nowhere
+                    let Some(next) = map.neighbors4(cur).find(|&n| n != prev && open(n)) else {
+                        break;
+                    };
+                    passable &= !slopes || downhill(map, cur, next);
+                    prev = cur;
+                    cells.push(next);
+                }
+            }
+        }
+        Ok(Self { junctions, trails })
+    }
+
+    pub fn junctions(&self) -> &[Pos] {
+        &self.junctions
+    }
+
+    // The junctions so that every trail leads to a later one, if no trail
+    // ever leads back to where it came from.
+    fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut incoming = vec![0; self.junctions.len()];
+        for trail in self.trails.iter().flatten() {
+            incoming[trail.to] += 1;
+        }
+        let mut order: Vec<usize> = (0..incoming.len()).filter(|j| incoming[*j] == 0).collect();
+        let mut ind = 0;
+        while let Some(&junction) = order.get(ind) {
+            ind += 1;
+            for trail in &self.trails[junction] {
+                incoming[trail.to] -= 1;
+                if incoming[trail.to] == 0 {
+                    order.push(trail.to);
+                }
+            }
+        }
+        (order.len() == self.junctions.len()).then_some(order)
+    }
+
+    // The longest hike when the slopes forbid going back: the longest
+    // distance to each junction follows from the ones before it.
+    fn longest_acyclic(&self, order: &[usize]) -> Option<Steps> {
+        // the length of the longest hike to each junction, and its last step
+        let mut longest: Vec<Option<usize>> = vec![None; self.junctions.len()];
+        let mut last = vec![None; self.junctions.len()];
+        longest[START] = Some(0);
+        for &junction in order {
+            let Some(len) = longest[junction] else {
+                continue;
+            };
+            for (ind, trail) in self.trails[junction].iter().enumerate() {
+                let len = len + trail.cells.len();
+                if longest[trail.to].is_none_or(|best| len > best) {
+                    longest[trail.to] = Some(len);
+                    last[trail.to] = Some((junction, ind));
+                }
+            }
+        }
+        longest[GOAL]?;
+        let mut steps = vec![];
+        let mut step = last[GOAL];
+        while let Some((junction, ind)) = step {
+            steps.push((junction, ind));
+            step = last[junction];
+        }
+        steps.reverse();
+        Some(steps)
+    }
+
+    fn longest_steps(&self, parallel: bool) -> Result<Option<Steps>, HikeError> {
+        if self.junctions[START] == self.junctions[GOAL] {
+            return Ok(Some(vec![]));
+        }
+        match (self.topological_order(), parallel) {
+            (Some(order), _) => Ok(self.longest_acyclic(&order)),
+            (None, false) => Ok(Search::new(self)?.run()?),
+            (None, true) => Ok(Search::new(self)?.run_parallel()?),
+        }
+    }
+
+    /// The length of the longest hike from the start to the goal that never
+    /// steps twice on the same cell, `None` when the goal cannot be
+    /// reached.
+    pub fn longest(&self, parallel: bool) -> Result<Option<usize>, HikeError> {
+        Ok(self.longest_route(parallel)?.map(|route| route.len() - 1))
+    }
+
+    /// The cells of the longest hike, from the start to the goal. When the
+    /// slopes forbid going back, the longest distance to each junction
+    /// follows from the ones before it; otherwise every hike is searched,
+    /// on every thread when `parallel` is set. Both searches find the same
+    /// route.
+    pub fn longest_route(&self, parallel: bool) -> Result<Option<Vec<Pos>>, HikeError> {
+        let Some(steps) = self.longest_steps(parallel)? else {
+            return Ok(None);
+        };
+        let mut route = vec![self.junctions[START]];
+        for (junction, ind) in steps {
+            route.extend(&self.trails[junction][ind].cells);
+        }
+        Ok(Some(route))
+    }
+}
+
+/// The map with the cells of the route marked `O`.
+pub fn render(map: &Grid<char>, route: &[Pos]) -> Grid<char> {
+    let mut rendered = map.clone();
+    for &pos in route {
+        rendered[pos] = 'O';
+    }
+    rendered
+}
+
+// The number of junctions the hikes go through before the parallel search
+// splits them between threads.
+const SPLIT_DEPTH: usize = 8;
+
+// Depth-first search of the hikes through the junctions, the visited ones
+// being the bits of a `u64`.
+struct Search<'t> {
+    trails: &'t Trails,
+    // the longest trail to each junction
+    longest_to: Vec<usize>,
+    // the only junction with a trail to the goal, if there is a single one:
+    // leaving it for another junction would make the goal unreachable
+    gate: Option<usize>,
+    // the longest hike found by any walk so far
+    bound: AtomicUsize,
+}
+
+// A hike being searched, up to `junction`, and the longest one found from
+// there.
+#[derive(Clone, Default)]
+struct Walk {
+    junction: usize,
+    visited: u64,
+    len: usize,
+    // the lengths of the longest trails to the junctions not visited yet
+    potential: usize,
+    steps: Steps,
+    best: Option<(usize, Steps)>,
+}
+
+impl<'t> Search<'t> {
+    fn new(trails: &'t Trails) -> Result<Self, HikeError> {
+        let count = trails.junctions.len();
+        if count > 64 {
+            return Err(HikeError::TooManyJunctions(count));
+        }
+        let mut longest_to = vec![0; count];
+        let mut to_goal = vec![];
+        for (from, leaving) in trails.trails.iter().enumerate() {
+            for trail in leaving {
+                longest_to[trail.to] = longest_to[trail.to].max(trail.cells.len());
+                if trail.to == GOAL && !to_goal.contains(&from) {
+                    to_goal.push(from);
+                }
+            }
+        }
+        Ok(Self {
+            trails,
+            longest_to,
+            gate: (to_goal.len() == 1).then(|| to_goal[0]),
+            bound: AtomicUsize::new(0),
+        })
+    }
+
+    fn start(&self) -> Walk {
+        // every junction left is entered at most once, through its longest
+        // trail at best
+        Walk {
+            junction: START,
+            visited: 1 << START,
+            potential: self.longest_to.iter().sum::<usize>() - self.longest_to[START],
+            ..Walk::default()
+        }
+    }
+
+    fn run(&self) -> Result<Option<Steps>, BudgetExceeded> {
+        let mut walk = self.start();
+        self.visit(&mut walk)?;
+        Ok(walk.best.map(|(_, steps)| steps))
+    }
+
+    // Searches the hikes from each of the first junctions on their own
+    // thread. The walks are in the order the sequential search would take
+    // them and the earliest longest hike wins, so both find the same route.
+    // The threads share the budget of the caller.
+    fn run_parallel(&self) -> Result<Option<Steps>, BudgetExceeded> {
+        let mut walks = vec![];
+        self.split(&mut self.start(), SPLIT_DEPTH, &mut walks);
+        let shared = budget::share();
+        let found = walks
+            .into_par_iter()
+            .map(|mut walk| {
+                budget::with_shared(&shared, || self.visit(&mut walk))?;
+                Ok(walk.best)
+            })
+            .collect::<Result<Vec<_>, BudgetExceeded>>()?;
+        Ok(found
+            .into_iter()
+            .flatten()
+            .reduce(|best, other| if other.0 > best.0 { other } else { best })
+            .map(|(_, steps)| steps))
+    }
+
+    // The trails the hike can take next, by their index among the ones
+    // leaving its junction.
+    fn next(&self, walk: &Walk) -> impl Iterator<Item = (usize, &'t Trail)> + 't {
+        let (junction, visited, gate) = (walk.junction, walk.visited, self.gate);
+        self.trails.trails[junction]
+            .iter()
+            .enumerate()
+            .filter(move |(_, trail)| {
+                visited & (1 << trail.to) == 0 && (gate != Some(junction) || trail.to == GOAL)
+            })
+    }
+
+    // Takes the trail to go on with the walk, and returns where it was.
+    fn step(&self, walk: &mut Walk, ind: usize, trail: &Trail) -> (usize, usize) {
+        walk.steps.push((walk.junction, ind));
+        let from = (walk.junction, walk.len);
+        walk.junction = trail.to;
+        walk.visited |= 1 << trail.to;
+        walk.len += trail.cells.len();
+        walk.potential -= self.longest_to[trail.to];
+        from
+    }
+
+    fn back(&self, walk: &mut Walk, (junction, len): (usize, usize)) {
+        walk.steps.pop();
+        walk.potential += self.longest_to[walk.junction];
+        walk.visited &= !(1 << walk.junction);
+        walk.junction = junction;
+        walk.len = len;
+    }
+
+    // The walks going through `depth` more junctions, or ending at the goal
+    // sooner.
+    fn split(&self, walk: &mut Walk, depth: usize, walks: &mut Vec<Walk>) {
+        if depth == 0 || walk.junction == GOAL {
+            walks.push(walk.clone());
+            return;
+        }
+        for (ind, trail) in self.next(walk) {
+            let from = self.step(walk, ind, trail);
+            self.split(walk, depth - 1, walks);
+            self.back(walk, from);
+        }
+    }
+
+    fn visit(&self, walk: &mut Walk) -> Result<(), BudgetExceeded> {
+        budget::tick(|| {
+            format!(
+                "the longest hike found so far is {} steps",
+                self.bound.load(Ordering::Relaxed)
+            )
+        })?;
+        let len = walk.len;
+        if walk.junction == GOAL {
+            if walk.best.as_ref().is_none_or(|(best, _)| len > *best) {
+                walk.best = Some((len, walk.steps.clone()));
+                self.bound.fetch_max(len, Ordering::Relaxed);
+            }
+            return Ok(());
+        }
+        // the hikes of other walks only prune the ones that are shorter, so
+        // that each walk still finds its first longest hike
+        let reach = len + walk.potential;
+        if walk.best.as_ref().is_some_and(|(best, _)| reach <= *best)
+            || reach < self.bound.load(Ordering::Relaxed)
+        {
+            return Ok(());
+        }
+        for (ind, trail) in self.next(walk) {
+            let from = self.step(walk, ind, trail);
+            self.visit(walk)?;
+            self.back(walk, from);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day23;
+    use common::{
+        budget::{with_budget, Budget},
+        generate::generate,
+        Solution,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn errors() {
+        let closed = Day23::parse("###\n#.#\n###\n").unwrap();
+        assert_eq!(
+            Trails::new(&closed, false).err(),
+            Some(HikeError::MissingOpening)
+        );
+        // a 9 by 9 lattice of junctions, but the top-right and bottom-left
+        // corners have two trails only, and the openings are junctions too
+        let map = Day23::parse(&crate::lattice(&mut StdRng::seed_from_u64(0), 9)).unwrap();
+        let with_slopes = Trails::new(&map, true).unwrap();
+        assert_eq!(with_slopes.junctions().len(), 81);
+        assert!(with_slopes.longest(false).unwrap().is_some());
+        assert_eq!(
+            Trails::new(&map, false).unwrap().longest(false),
+            Err(HikeError::TooManyJunctions(81))
+        );
+        assert_eq!(
+            Trails::between(&map, Pos::new(0, 1), Pos::new(0, 0), false).err(),
+            Some(HikeError::Blocked(Pos::new(0, 0)))
+        );
+        assert_eq!(
+            HikeError::Blocked(Pos::new(0, 0)).to_string(),
+            "cannot hike from or to line 1, column 1"
+        );
+    }
+
+    #[test]
+    fn routes() {
+        let map = Day23::parse(
+            "\
+#.###
+#...#
+#.#.#
+#...#
+###.#
+",
+        )
+        .unwrap();
+        let trails = Trails::new(&map, false).unwrap();
+        let route = trails.longest_route(false).unwrap().unwrap();
+        assert_eq!(route.len(), 7);
+        assert_eq!((route[0], route[6]), (Pos::new(0, 1), Pos::new(4, 3)));
+        assert!(route
+            .windows(2)
+            .all(|w| w[0].r.abs_diff(w[1].r) + w[0].c.abs_diff(w[1].c) == 1));
+        assert_eq!(
+            render(&map, &route).to_string(),
+            "#O###\n#OOO#\n#.#O#\n#..O#\n###O#\n"
+        );
+
+        // between cells in the middle of trails, the long way round
+        let trails = Trails::between(&map, Pos::new(2, 1), Pos::new(1, 3), false).unwrap();
+        assert_eq!(trails.longest(false).unwrap(), Some(5));
+        let trails = Trails::between(&map, Pos::new(2, 3), Pos::new(2, 3), false).unwrap();
+        assert_eq!(
+            trails.longest_route(false).unwrap(),
+            Some(vec![Pos::new(2, 3)])
+        );
+    }
+
+    #[test]
+    fn parallel() {
+        for seed in 0..4 {
+            let map = Day23::parse(&generate::<Day23>(seed, 5)).unwrap();
+            let trails = Trails::new(&map, false).unwrap();
+            let route = trails.longest_route(false).unwrap();
+            assert!(route.is_some());
+            assert_eq!(trails.longest_route(true).unwrap(), route);
+        }
+    }
+
+    #[test]
+    fn budget() {
+        let map = Day23::parse(&generate::<Day23>(0, 5)).unwrap();
+        let trails = Trails::new(&map, false).unwrap();
+        let budget = Budget {
+            time: None,
+            steps: Some(100),
+        };
+        for parallel in [false, true] {
+            match with_budget(budget, || trails.longest(parallel)) {
+                Err(HikeError::Budget(err)) => assert_eq!(err.steps, 100),
+                other => panic!("expected the budget to run out, got {:?}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use common::Solution;
+use day24::{Crossing, Day24, TEST_AREA};
+use std::{fs, path::PathBuf};
+
+/// Sorts the pairs of hailstones by how their paths cross in the xy plane,
+/// and counts the crossings ahead of both inside a test area.
+#[derive(Parser)]
+struct Args {
+    /// Puzzle input
+    #[arg(default_value = "input.txt")]
+    input: PathBuf,
+    /// Smallest coordinate of the test area on both axes
+    #[arg(long, allow_negative_numbers = true, default_value_t = *TEST_AREA.start())]
+    min: i64,
+    /// Largest coordinate of the test area on both axes
+    #[arg(long, allow_negative_numbers = true, default_value_t = *TEST_AREA.end())]
+    max: i64,
+    /// Writes every pair and how it crosses to standard error
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    common::output::set_verbosity(args.verbose);
+    let path = args.input.display();
+    let content = fs::read_to_string(&args.input).with_context(|| format!("reading {}", path))?;
+    let hailstones = Day24::parse(&content).with_context(|| format!("parsing {}", path))?;
+
+    let mut counts = [0_usize; 4];
+    for i in 0..hailstones.len() {
+        for j in i + 1..hailstones.len() {
+            let crossing = hailstones[i]
+                .crossing(&hailstones[j])
+                .with_context(|| format!("crossing hailstones {} and {}", i, j))?;
+            common::debug!("Hailstones {} and {}: {}", i, j, crossing);
+            counts[match crossing {
+                Crossing::Future(..) => 0,
+                Crossing::Past(..) => 1,
+                Crossing::Parallel => 2,
+                Crossing::Coincident => 3,
+            }] += 1;
+        }
+    }
+    println!("Ahead of both: {}", counts[0]);
+    println!("In the past: {}", counts[1]);
+    println!("Parallel: {}", counts[2]);
+    println!("Coincident: {}", counts[3]);
+    println!(
+        "Ahead of both inside {}..={}: {}",
+        args.min,
+        args.max,
+        day24::intersections(&hailstones, args.min..=args.max)?
+    );
+    Ok(())
+}
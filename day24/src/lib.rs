@@ -0,0 +1,682 @@
+use anyhow::Result;
+use common::{
+    budget,
+    generate::Generate,
+    numtheory::{gcd, NumberTheoryError, Ratio},
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{collections::HashSet, error, fmt, ops::RangeInclusive};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingAt(Span),
+    InvalidVector(Span),
+    InvalidNumber(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingAt(s) => write!(f, "expected `position @ velocity`, got {}", s),
+            ParseError::InvalidVector(s) => write!(f, "expected three components in {}", s),
+            ParseError::InvalidNumber(s) => write!(f, "invalid number {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+pub struct Hailstone {
+    x: i64,
+    y: i64,
+    z: i64,
+    vx: i64,
+    vy: i64,
+    vz: i64,
+}
+
+fn vector(s: Token) -> Result<[i64; 3], ParseError> {
+    let values = s
+        .split(",")
+        .map(|v| v.trim().parse().map_err(ParseError::InvalidNumber))
+        .collect::<Result<Vec<i64>, _>>()?;
+    values
+        .try_into()
+        .map_err(|_| ParseError::InvalidVector(s.span()))
+}
+
+impl Hailstone {
+    fn parse(line: Token) -> Result<Self, ParseError> {
+        let (pos, vel) = line
+            .split_once("@")
+            .ok_or_else(|| ParseError::MissingAt(line.span()))?;
+        let [x, y, z] = vector(pos)?;
+        let [vx, vy, vz] = vector(vel)?;
+        Ok(Self {
+            x,
+            y,
+            z,
+            vx,
+            vy,
+            vz,
+        })
+    }
+
+    fn position(&self) -> [i128; 3] {
+        [self.x, self.y, self.z].map(i128::from)
+    }
+
+    fn velocity(&self) -> [i128; 3] {
+        [self.vx, self.vy, self.vz].map(i128::from)
+    }
+}
+
+/// How the paths of two hailstones meet in the xy plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crossing {
+    /// The paths cross at `(x, y)`, where both hailstones are yet to go.
+    Future(Ratio, Ratio),
+    /// The paths cross at `(x, y)`, where one hailstone or both already
+    /// went.
+    Past(Ratio, Ratio),
+    /// The paths never cross.
+    Parallel,
+    /// The paths are the same line, or a hailstone that does not move in
+    /// the plane stands on the path of the other one.
+    Coincident,
+}
+
+impl fmt::Display for Crossing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Crossing::Future(x, y) => write!(f, "cross ahead at x={}, y={}", x, y),
+            Crossing::Past(x, y) => write!(f, "crossed in the past at x={}, y={}", x, y),
+            Crossing::Parallel => write!(f, "parallel"),
+            Crossing::Coincident => write!(f, "coincident"),
+        }
+    }
+}
+
+// The arithmetic on coordinates is checked: the products of coordinates
+// and velocities near the limits of `i64` do not fit into `i128`.
+fn mul(a: i128, b: i128) -> Result<i128, NumberTheoryError> {
+    a.checked_mul(b).ok_or(NumberTheoryError::Overflow)
+}
+
+fn add(a: i128, b: i128) -> Result<i128, NumberTheoryError> {
+    a.checked_add(b).ok_or(NumberTheoryError::Overflow)
+}
+
+fn sub(a: i128, b: i128) -> Result<i128, NumberTheoryError> {
+    a.checked_sub(b).ok_or(NumberTheoryError::Overflow)
+}
+
+// The z component of the cross product, for vectors in the xy plane.
+fn cross(a: [i128; 3], b: [i128; 3]) -> Result<i128, NumberTheoryError> {
+    sub(mul(a[0], b[1])?, mul(a[1], b[0])?)
+}
+
+fn cross3(a: [i128; 3], b: [i128; 3]) -> Result<[i128; 3], NumberTheoryError> {
+    let term = |i: usize, j: usize| sub(mul(a[i], b[j])?, mul(a[j], b[i])?);
+    Ok([term(1, 2)?, term(2, 0)?, term(0, 1)?])
+}
+
+fn dot(a: [i128; 3], b: [i128; 3]) -> Result<i128, NumberTheoryError> {
+    (0..3).try_fold(0, |acc, i| add(acc, mul(a[i], b[i])?))
+}
+
+fn sub3(a: [i128; 3], b: [i128; 3]) -> Result<[i128; 3], NumberTheoryError> {
+    Ok([sub(a[0], b[0])?, sub(a[1], b[1])?, sub(a[2], b[2])?])
+}
+
+fn add3(a: [i128; 3], b: [i128; 3]) -> Result<[i128; 3], NumberTheoryError> {
+    Ok([add(a[0], b[0])?, add(a[1], b[1])?, add(a[2], b[2])?])
+}
+
+fn scale3(a: [i128; 3], t: i128) -> Result<[i128; 3], NumberTheoryError> {
+    Ok([mul(a[0], t)?, mul(a[1], t)?, mul(a[2], t)?])
+}
+
+impl Hailstone {
+    /// How the path of this hailstone meets the one of `other`, ignoring
+    /// the z axis, or `Overflow` if the coordinates are too large to tell.
+    pub fn crossing(&self, other: &Hailstone) -> Result<Crossing, NumberTheoryError> {
+        let (p, u) = (self.position(), self.velocity());
+        let (q, v) = (other.position(), other.velocity());
+        let d = [q[0] - p[0], q[1] - p[1], 0];
+        let det = cross(u, v)?;
+        if det == 0 {
+            let still = u[..2] == [0, 0] && v[..2] == [0, 0];
+            let on_both = cross(d, u)? == 0 && cross(d, v)? == 0;
+            return Ok(match on_both && (!still || d == [0; 3]) {
+                true => Crossing::Coincident,
+                false => Crossing::Parallel,
+            });
+        }
+        // `p + s * u = q + t * v`, each cross product with `u` or `v`
+        // leaves a single unknown
+        let (s, t) = (cross(d, v)?, cross(d, u)?);
+        let x = Ratio::new(add(mul(p[0], det)?, mul(s, u[0])?)?, det);
+        let y = Ratio::new(add(mul(p[1], det)?, mul(s, u[1])?)?, det);
+        Ok(
+            match s.signum() * det.signum() >= 0 && t.signum() * det.signum() >= 0 {
+                true => Crossing::Future(x, y),
+                false => Crossing::Past(x, y),
+            },
+        )
+    }
+}
+
+/// The test area of the puzzle, on both axes.
+pub const TEST_AREA: RangeInclusive<i64> = 200000000000000..=400000000000000;
+
+/// Counts the pairs of hailstones whose paths cross inside the test area,
+/// ahead of both hailstones.
+pub fn intersections(
+    hailstones: &[Hailstone],
+    area: RangeInclusive<i64>,
+) -> Result<usize, NumberTheoryError> {
+    let area = Ratio::from(*area.start())..=Ratio::from(*area.end());
+    let mut count = 0_usize;
+    for i in 0..hailstones.len() {
+        for j in i + 1..hailstones.len() {
+            if let Crossing::Future(x, y) = hailstones[i].crossing(&hailstones[j])? {
+                if area.contains(&x) && area.contains(&y) {
+                    count += 1;
+                }
+            }
+        }
+    }
+    Ok(count)
+}
+
+/// How two hailstones meet in space from time 0 on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encounter {
+    /// They hit each other at `time`.
+    Collision(Ratio),
+    /// They are at the same place all along.
+    Together,
+    /// They never hit, and are the closest at `time`, `squared_distance`
+    /// being the square of the distance between them then.
+    Approach {
+        time: Ratio,
+        squared_distance: Ratio,
+    },
+}
+
+impl Encounter {
+    /// The distance between the hailstones when they are the closest.
+    pub fn distance(&self) -> f64 {
+        match self {
+            Encounter::Approach {
+                squared_distance, ..
+            } => squared_distance.to_f64().sqrt(),
+            _ => 0_f64,
+        }
+    }
+}
+
+impl Hailstone {
+    /// When this hailstone and `other` collide in 3D, or how close they
+    /// get. Seen from this hailstone, `other` starts at `d` and moves at
+    /// `w`, so it is at `d + t * w` at time `t`. `Overflow` if the
+    /// coordinates are too large to tell.
+    pub fn encounter(&self, other: &Hailstone) -> Result<Encounter, NumberTheoryError> {
+        let (p, u) = (self.position(), self.velocity());
+        let (q, v) = (other.position(), other.velocity());
+        let d = sub3(q, p)?;
+        let w = sub3(v, u)?;
+        let (dw, ww) = (dot(d, w)?, dot(w, w)?);
+        if d == [0; 3] {
+            return Ok(match ww {
+                0 => Encounter::Together,
+                _ => Encounter::Collision(Ratio::from(0_i64)),
+            });
+        }
+        // the distance is the smallest when `d + t * w` is orthogonal to
+        // `w`, the hailstones already drift apart if that was in the past
+        if ww == 0 || dw >= 0 {
+            return Ok(Encounter::Approach {
+                time: Ratio::from(0_i64),
+                squared_distance: Ratio::from(dot(d, d)?),
+            });
+        }
+        let time = Ratio::new(-dw, ww);
+        // |d + t * w|² = |d|² - (d·w)² / |w|² at the closest
+        let squared_distance = Ratio::new(sub(mul(dot(d, d)?, ww)?, mul(dw, dw)?)?, ww);
+        Ok(match squared_distance.numer() {
+            0 => Encounter::Collision(time),
+            _ => Encounter::Approach {
+                time,
+                squared_distance,
+            },
+        })
+    }
+}
+
+/// The collisions between hailstones within the `window` of time, in time
+/// order, as the time and the indices of both hailstones. Pairs that are
+/// together all along collide at the start of the window. Like
+/// `Hailstone::encounter`, the window only covers time 0 on.
+///
+/// Only the pairs whose x coordinates overlap during the window can
+/// collide: the hailstones are swept by the smallest x they reach, keeping
+/// those whose largest x is not behind.
+pub fn collisions(
+    hailstones: &[Hailstone],
+    window: RangeInclusive<u64>,
+) -> Result<Vec<(Ratio, usize, usize)>, NumberTheoryError> {
+    let (start, end) = (i128::from(*window.start()), i128::from(*window.end()));
+    let mut extents = hailstones
+        .iter()
+        .enumerate()
+        .map(|(ind, h)| {
+            let (x, vx) = (i128::from(h.x), i128::from(h.vx));
+            let (a, b) = (add(x, mul(vx, start)?)?, add(x, mul(vx, end)?)?);
+            Ok((a.min(b), a.max(b), ind))
+        })
+        .collect::<Result<Vec<(i128, i128, usize)>, NumberTheoryError>>()?;
+    extents.sort();
+
+    let mut found = vec![];
+    let mut active: Vec<(i128, usize)> = vec![];
+    for (low, high, ind) in extents {
+        active.retain(|&(other_high, _)| other_high >= low);
+        for &(_, other) in &active {
+            let (i, j) = (other.min(ind), other.max(ind));
+            let time = match hailstones[i].encounter(&hailstones[j])? {
+                Encounter::Collision(time) => time,
+                Encounter::Together => Ratio::from(start),
+                Encounter::Approach { .. } => continue,
+            };
+            if time >= Ratio::from(start) && time <= Ratio::from(end) {
+                found.push((time, i, j));
+            }
+        }
+        active.push((high, ind));
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Where the rock is thrown from, and how fast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rock {
+    pub position: [i128; 3],
+    pub velocity: [i128; 3],
+}
+
+// When a hailstone at `offset` from the rock, moving at `relative` velocity
+// from it, is hit, on the given axes: `Some(None)` when they always are at
+// the same place, `None` when they never meet at a non-negative integer
+// time.
+fn meeting(offset: &[i128], relative: &[i128]) -> Option<Option<i128>> {
+    let mut time = None;
+    for (&d, &u) in offset.iter().zip(relative) {
+        match u {
+            0 if d != 0 => return None,
+            0 => {}
+            _ if d % u != 0 || -d / u < 0 => return None,
+            _ => match time {
+                Some(t) if t != -d / u => return None,
+                _ => time = Some(-d / u),
+            },
+        }
+    }
+    Some(time)
+}
+
+fn relative(h: &Hailstone, position: &[i128], velocity: &[i128]) -> ([i128; 3], [i128; 3]) {
+    let (p, v) = (h.position(), h.velocity());
+    (
+        [0, 1, 2].map(|i| p[i] - position.get(i).copied().unwrap_or(0)),
+        [0, 1, 2].map(|i| v[i] - velocity.get(i).copied().unwrap_or(0)),
+    )
+}
+
+impl Rock {
+    /// Whether the rock hits every hailstone at a non-negative integer time.
+    pub fn hits_all(&self, hailstones: &[Hailstone]) -> bool {
+        hailstones.iter().all(|h| {
+            let (offset, rel) = relative(h, &self.position, &self.velocity);
+            meeting(&offset, &rel).is_some()
+        })
+    }
+}
+
+// The time the rock, going through the origin along `direction`, hits a
+// hailstone at `p` moving at `v`: `p + t * v` is then parallel to the
+// direction. `None` if the hailstone moves along the rock or is hit at no
+// integer time.
+fn hit_time(
+    p: [i128; 3],
+    v: [i128; 3],
+    direction: [i128; 3],
+) -> Result<Option<i128>, NumberTheoryError> {
+    let (a, b) = (cross3(p, direction)?, cross3(v, direction)?);
+    Ok((0..3)
+        .find(|&i| b[i] != 0)
+        .filter(|&i| a[i] % b[i] == 0)
+        .map(|i| -a[i] / b[i]))
+}
+
+// The rock hitting the hailstones `i` and `j` as seen from the first one,
+// which then stands still at the origin. The rock goes through the origin
+// and lies in the plane of the origin and the path of each of the two, so
+// it moves along the line where both planes meet. The times it hits `i`
+// and `j` on that line give the rock's velocity and position.
+fn throw_from(
+    hailstones: &[Hailstone],
+    i: usize,
+    j: usize,
+) -> Result<Option<Rock>, NumberTheoryError> {
+    let (p0, v0) = (hailstones[0].position(), hailstones[0].velocity());
+    let frame = |h: &Hailstone| {
+        Ok::<_, NumberTheoryError>((sub3(h.position(), p0)?, sub3(h.velocity(), v0)?))
+    };
+    let ((pi, vi), (pj, vj)) = (frame(&hailstones[i])?, frame(&hailstones[j])?);
+    let mut direction = cross3(cross3(pi, vi)?, cross3(pj, vj)?)?;
+    if direction == [0; 3] {
+        return Ok(None);
+    }
+    let divisor = direction.into_iter().try_fold(0, gcd)?;
+    direction = direction.map(|c| c / divisor);
+    let (Some(ti), Some(tj)) = (hit_time(pi, vi, direction)?, hit_time(pj, vj, direction)?) else {
+        return Ok(None);
+    };
+    if ti == tj || ti < 0 || tj < 0 {
+        return Ok(None);
+    }
+    let at = |h: &Hailstone, t: i128| add3(h.position(), scale3(h.velocity(), t)?);
+    let (xi, xj) = (at(&hailstones[i], ti)?, at(&hailstones[j], tj)?);
+    let gap = sub3(xj, xi)?;
+    if gap.iter().any(|c| c % (tj - ti) != 0) {
+        return Ok(None);
+    }
+    let velocity = gap.map(|c| c / (tj - ti));
+    let position = sub3(xi, scale3(velocity, ti)?)?;
+    Ok(Some(Rock { position, velocity }))
+}
+
+/// Finds the rock throw hitting every hailstone. Seen from the first
+/// hailstone, two more hailstones whose paths are not in the same plane
+/// through it fix the rock's path, which must then hit all of them. If
+/// the rock always stays with the first hailstone, every other path goes
+/// through it instead.
+pub fn rock_throw(hailstones: &[Hailstone]) -> Result<Rock> {
+    if hailstones.len() < 3 {
+        anyhow::bail!("at least three hailstones are needed to tell the rock throw");
+    }
+    let along = Rock {
+        position: hailstones[0].position(),
+        velocity: hailstones[0].velocity(),
+    };
+    if along.hits_all(hailstones) {
+        return Ok(along);
+    }
+    for i in 1..hailstones.len() {
+        for j in i + 1..hailstones.len() {
+            budget::tick(|| {
+                format!(
+                    "no rock throw found from hailstones {} and {}",
+                    i + 1,
+                    j + 1
+                )
+            })?;
+            if let Some(rock) = throw_from(hailstones, i, j)? {
+                if rock.hits_all(hailstones) {
+                    return Ok(rock);
+                }
+                anyhow::bail!("no rock throw hits every hailstone");
+            }
+        }
+    }
+    anyhow::bail!("the hailstones do not tell a single rock throw")
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input = Vec<Hailstone>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Token::lines(input)
+            .filter(|l| !l.is_empty())
+            .map(Hailstone::parse)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(intersections(input, TEST_AREA)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let rock = rock_throw(input)?;
+        Ok(rock.position.iter().sum::<i128>().into())
+    }
+}
+
+// A velocity component that is never zero, so that no path is parallel to
+// an axis.
+fn speed<R: Rng>(rng: &mut R) -> i64 {
+    rng.gen_range(1..=300) * if rng.gen_bool(0.5) { 1 } else { -1 }
+}
+
+impl Generate for Day24 {
+    // Like in the real puzzles, a rock thrown from an integer position with
+    // an integer velocity hits every hailstone, each at a different time.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let rock = [(); 3].map(|_| rng.gen_range(200_000_000_000_000..400_000_000_000_000_i64));
+        let throw = [(); 3].map(|_| speed(rng));
+        let mut times = HashSet::new();
+        let mut r = String::new();
+        while times.len() < size.max(3) {
+            let t = rng.gen_range(100_000_000_000..500_000_000_000_i64);
+            let velocity = [(); 3].map(|_| speed(rng));
+            if (0..3).any(|i| velocity[i] == throw[i]) || !times.insert(t) {
+                continue;
+            }
+            let [x, y, z] = [0, 1, 2].map(|i| rock[i] + (throw[i] - velocity[i]) * t);
+            let [vx, vy, vz] = velocity;
+            r += &format!("{}, {}, {} @ {}, {}, {}\n", x, y, z, vx, vy, vz);
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
+
+    #[test]
+    fn example() {
+        let hailstones = Day24::parse(EXAMPLE).unwrap();
+        assert_eq!(intersections(&hailstones, 7..=27).unwrap(), 2);
+        let rock = rock_throw(&hailstones).unwrap();
+        assert_eq!(rock.position, [24, 13, 10]);
+        assert_eq!(rock.velocity, [-3, 1, 2]);
+        assert!(!Rock {
+            velocity: [-3, 1, 1],
+            ..rock
+        }
+        .hits_all(&hailstones));
+    }
+
+    #[test]
+    fn crossings() {
+        let hailstones = Day24::parse(EXAMPLE).unwrap();
+        let (a, b) = (&hailstones[0], &hailstones[1]);
+        assert_eq!(
+            a.crossing(b).unwrap(),
+            Crossing::Future(Ratio::new(43, 3), Ratio::new(46, 3))
+        );
+        assert_eq!(
+            a.crossing(&hailstones[4]).unwrap(),
+            Crossing::Past(Ratio::new(193, 9), Ratio::new(106, 9))
+        );
+        assert_eq!(
+            hailstones[1].crossing(&hailstones[2]).unwrap(),
+            Crossing::Parallel
+        );
+        let parse = |s: &str| Day24::parse(s).unwrap().remove(0);
+        let (on, along) = (parse("21, 12, 0 @ 4, -2, 0"), parse("23, 11, 5 @ 0, 0, 1"));
+        assert_eq!(a.crossing(&on).unwrap(), Crossing::Coincident);
+        assert_eq!(a.crossing(&along).unwrap(), Crossing::Coincident);
+        assert_eq!(along.crossing(&along).unwrap(), Crossing::Coincident);
+        assert_eq!(
+            along.crossing(&parse("0, 0, 0 @ 0, 0, 1")).unwrap(),
+            Crossing::Parallel
+        );
+    }
+
+    #[test]
+    fn encounters() {
+        let hailstones = Day24::parse(
+            "0, 0, 0 @ 1, 1, 1
+10, 0, 0 @ -1, 1, 1
+0, 10, 0 @ 1, 0, 0
+5, 5, 5 @ 0, 0, 0
+20, 0, 0 @ -2, 1, 1
+1, 1, 1 @ 1, 1, 1
+",
+        )
+        .unwrap();
+        let at = |t: i64| Ratio::from(t);
+        let (a, b, c, f) = (
+            &hailstones[0],
+            &hailstones[1],
+            &hailstones[2],
+            &hailstones[5],
+        );
+        assert_eq!(a.encounter(b).unwrap(), Encounter::Collision(at(5)));
+        let approach = a.encounter(c).unwrap();
+        assert_eq!(
+            approach,
+            Encounter::Approach {
+                time: at(5),
+                squared_distance: at(50)
+            }
+        );
+        assert_eq!(approach.distance(), 50_f64.sqrt());
+        // side by side all along
+        assert_eq!(a.encounter(f).unwrap().distance(), 3_f64.sqrt());
+        assert_eq!(a.encounter(a).unwrap(), Encounter::Together);
+        assert_eq!(
+            collisions(&hailstones, 0..=100).unwrap(),
+            [
+                (at(4), 3, 5),
+                (at(5), 0, 1),
+                (at(5), 0, 3),
+                (at(5), 1, 3),
+                (Ratio::new(20, 3), 0, 4),
+                (at(10), 1, 4),
+            ]
+        );
+        assert_eq!(
+            collisions(&hailstones, 6..=9).unwrap(),
+            [(Ratio::new(20, 3), 0, 4)]
+        );
+
+        // thrown from the same place at different speeds
+        let apart = Day24::parse("1, 2, 3 @ 1, 0, 0\n1, 2, 3 @ 2, 0, 0\n").unwrap();
+        assert_eq!(
+            apart[0].encounter(&apart[1]).unwrap(),
+            Encounter::Collision(at(0))
+        );
+        assert_eq!(collisions(&apart, 0..=10).unwrap(), [(at(0), 0, 1)]);
+        assert_eq!(collisions(&apart, 1..=10).unwrap(), []);
+    }
+
+    #[test]
+    fn large_coordinates() {
+        let near = Day24::parse(
+            "9223372036854775000, 9223372036854775000, 0 @ -1, 0, 0
+9223372036854774000, 9223372036854774000, 0 @ 0, 1, 0
+",
+        )
+        .unwrap();
+        let (x, y) = (9223372036854774000_i64, 9223372036854775000_i64);
+        assert_eq!(
+            near[0].crossing(&near[1]),
+            Ok(Crossing::Future(Ratio::from(x), Ratio::from(y)))
+        );
+        assert_eq!(
+            near[0].encounter(&near[1]),
+            Ok(Encounter::Collision(Ratio::from(1000_i64)))
+        );
+
+        // the products of these coordinates and velocities overflow
+        let fast = Day24::parse(
+            "0, 0, 0 @ 9223372036854775807, -9223372036854775807, 1
+1, 9223372036854775807, 0 @ -9223372036854775807, -9223372036854775807, 1
+",
+        )
+        .unwrap();
+        assert_eq!(fast[0].crossing(&fast[1]), Err(NumberTheoryError::Overflow));
+        assert_eq!(
+            fast[0].encounter(&fast[1]),
+            Err(NumberTheoryError::Overflow)
+        );
+        assert_eq!(
+            intersections(&fast, 0..=1),
+            Err(NumberTheoryError::Overflow)
+        );
+        assert_eq!(collisions(&fast, 0..=1), Err(NumberTheoryError::Overflow));
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day24>(input, Part::One).unwrap(),
+            Answer::Number(15889)
+        );
+        assert_eq!(
+            solve::<Day24>(input, Part::Two).unwrap(),
+            Answer::Number(801386475216902)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day24>(0..10, 100).unwrap();
+        // with few hailstones the rock is often faster than all of them
+        for size in 1..=4 {
+            solve_generated::<Day24>(0..100, size).unwrap();
+        }
+    }
+
+    #[test]
+    fn fast_throw() {
+        // generated with seed 11, the rock is faster than twice any hailstone
+        let hailstones = Day24::parse(
+            "\
+240286508155002, 345973375566829, 284342211345014 @ -20, 36, 1
+219947987071344, 338430231442861, 268926414369022 @ 58, 42, 77
+212152016745202, 350489883640862, 267285550834444 @ 81, 114, 9
+",
+        )
+        .unwrap();
+        let rock = rock_throw(&hailstones).unwrap();
+        assert_eq!(
+            rock.position,
+            [218070930659272, 279068322411084, 310690919537624]
+        );
+        assert_eq!(rock.velocity, [66, 295, -101]);
+        assert!(rock_throw(&hailstones[..2]).is_err());
+    }
+}
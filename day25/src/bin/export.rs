@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use common::Solution;
+use day25::{export, Day25};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// Writes the wiring diagram with the two groups left by the smallest cut
+/// coloured and the wires of the cut highlighted.
+#[derive(Parser)]
+struct Args {
+    /// Puzzle input
+    #[arg(default_value = "input.txt")]
+    input: PathBuf,
+    /// Graphviz file to write
+    #[arg(long)]
+    dot: Option<PathBuf>,
+    /// GraphML file to write
+    #[arg(long)]
+    graphml: Option<PathBuf>,
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>> {
+    let file = File::create(path).with_context(|| format!("creating {}", path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    if args.dot.is_none() && args.graphml.is_none() {
+        anyhow::bail!("nothing to write, choose --dot or --graphml");
+    }
+    let path = args.input.display();
+    let content =
+        std::fs::read_to_string(&args.input).with_context(|| format!("reading {}", path))?;
+    let graph = Day25::parse(&content).with_context(|| format!("parsing {}", path))?;
+    let cut = graph.min_cut()?;
+    if let Some(path) = &args.dot {
+        let mut out = create(path)?;
+        export::write_dot(&mut out, &graph, &cut)?;
+        out.flush()?;
+    }
+    if let Some(path) = &args.graphml {
+        let mut out = create(path)?;
+        export::write_graphml(&mut out, &graph, &cut)?;
+        out.flush()?;
+    }
+    let [first, second] = cut.sizes();
+    println!(
+        "Cut of {} wires into groups of {} and {} components",
+        cut.edges.len(),
+        first,
+        second
+    );
+    Ok(())
+}
@@ -0,0 +1,503 @@
+use crate::Graph;
+use common::budget::{self, BudgetExceeded};
+use std::{collections::VecDeque, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CutError {
+    /// There are fewer components than parts to split them into.
+    TooManyParts {
+        parts: usize,
+        components: usize,
+    },
+    Budget(BudgetExceeded),
+}
+
+impl fmt::Display for CutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CutError::TooManyParts { parts, components } => write!(
+                f,
+                "cannot split {} components into {} parts",
+                components, parts
+            ),
+            CutError::Budget(_) => write!(f, "the search for a cut was stopped"),
+        }
+    }
+}
+
+impl error::Error for CutError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            CutError::Budget(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<BudgetExceeded> for CutError {
+    fn from(err: BudgetExceeded) -> Self {
+        CutError::Budget(err)
+    }
+}
+
+/// The fewest wires that split a graph in two, and the components on each
+/// side of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    pub edges: Vec<(u32, u32)>,
+    pub sides: [Vec<u32>; 2],
+}
+
+impl Cut {
+    pub fn sizes(&self) -> [usize; 2] {
+        [self.sides[0].len(), self.sides[1].len()]
+    }
+}
+
+/// Wires that split a graph into several groups of components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    pub edges: Vec<(u32, u32)>,
+    pub parts: Vec<Vec<u32>>,
+}
+
+impl Partition {
+    pub fn sizes(&self) -> Vec<usize> {
+        self.parts.iter().map(Vec::len).collect()
+    }
+}
+
+// The connected groups of the nodes kept by `member`, each in breadth-first
+// order.
+fn groups(graph: &Graph, member: &[bool]) -> Vec<Vec<u32>> {
+    let mut seen = vec![false; graph.len()];
+    let mut groups = vec![];
+    for start in 0..graph.len() as u32 {
+        if seen[start as usize] || !member[start as usize] {
+            continue;
+        }
+        seen[start as usize] = true;
+        let mut order = vec![start];
+        let mut ind = 0;
+        while let Some(&node) = order.get(ind) {
+            ind += 1;
+            for (next, _) in graph.neighbors(node) {
+                if !seen[next as usize] && member[next as usize] {
+                    seen[next as usize] = true;
+                    order.push(next);
+                }
+            }
+        }
+        groups.push(order);
+    }
+    groups
+}
+
+// The cut between `side` and the other nodes kept by `member`.
+fn cut(graph: &Graph, member: &[bool], side: &[u32]) -> Cut {
+    let mut inside = vec![false; graph.len()];
+    for &node in side {
+        inside[node as usize] = true;
+    }
+    let edges = graph
+        .edges()
+        .iter()
+        .filter(|(a, b)| {
+            let (a, b) = (*a as usize, *b as usize);
+            member[a] && member[b] && inside[a] != inside[b]
+        })
+        .copied()
+        .collect();
+    let [mut first, mut second] = [vec![], vec![]];
+    for node in (0..graph.len() as u32).filter(|n| member[*n as usize]) {
+        match inside[node as usize] {
+            false => first.push(node),
+            true => second.push(node),
+        }
+    }
+    Cut {
+        edges,
+        sides: [first, second],
+    }
+}
+
+// Unit flows through the wires towards a sink, from any node of a growing
+// source set, among the nodes kept by `member`.
+struct Flow<'g> {
+    graph: &'g Graph,
+    member: &'g [bool],
+    source: Vec<bool>,
+    // +1 when going from the first end of the wire to the second one
+    flow: Vec<i8>,
+    used: Vec<u32>,
+    // the search that reached each node, and the wire it came from
+    seen: Vec<(u32, u32)>,
+    search: u32,
+}
+
+impl<'g> Flow<'g> {
+    fn new(graph: &'g Graph, member: &'g [bool]) -> Self {
+        Self {
+            graph,
+            member,
+            source: vec![false; graph.len()],
+            flow: vec![0; graph.edges().len()],
+            used: vec![],
+            seen: vec![(0, 0); graph.len()],
+            search: 0,
+        }
+    }
+
+    fn residual(&self, from: u32, wire: u32) -> bool {
+        let flow = self.flow[wire as usize];
+        match self.graph.edges()[wire as usize].0 == from {
+            true => flow < 1,
+            false => flow > -1,
+        }
+    }
+
+    // Pushes one more unit to `sink` along a path found by searching
+    // backwards from it, so that the search stays around the sink when
+    // the source set is large. Returns the nodes that can still send flow
+    // to the sink when there is no such path.
+    fn augment(&mut self, sink: u32) -> Result<(), Vec<u32>> {
+        self.search += 1;
+        self.seen[sink as usize] = (self.search, u32::MAX);
+        let mut queue = VecDeque::from([sink]);
+        let mut reached = vec![sink];
+        while let Some(node) = queue.pop_front() {
+            for (prev, wire) in self.graph.neighbors(node) {
+                let p = prev as usize;
+                if !self.member[p] || self.seen[p].0 == self.search || !self.residual(prev, wire) {
+                    continue;
+                }
+                self.seen[p] = (self.search, wire);
+                if self.source[p] {
+                    self.push(prev, sink);
+                    return Ok(());
+                }
+                queue.push_back(prev);
+                reached.push(prev);
+            }
+        }
+        Err(reached)
+    }
+
+    fn push(&mut self, mut node: u32, sink: u32) {
+        while node != sink {
+            let wire = self.seen[node as usize].1;
+            let (a, b) = self.graph.edges()[wire as usize];
+            self.flow[wire as usize] += if a == node { 1 } else { -1 };
+            self.used.push(wire);
+            node = if a == node { b } else { a };
+        }
+    }
+
+    // The largest flow to `sink`, up to `cap`, and the nodes that can
+    // still send flow to the sink when it is below the cap.
+    fn max_flow(&mut self, sink: u32, cap: usize) -> (usize, Option<Vec<u32>>) {
+        let mut value = 0;
+        while value < cap {
+            match self.augment(sink) {
+                Ok(()) => value += 1,
+                Err(side) => return (value, Some(side)),
+            }
+        }
+        (value, None)
+    }
+
+    fn reset(&mut self) {
+        for wire in self.used.drain(..) {
+            self.flow[wire as usize] = 0;
+        }
+    }
+
+    // Puts `node` inside the group around the sink, with every node that
+    // could send it flow, or outside with every node it could send flow to.
+    fn decide(&self, inside: &mut [Option<bool>], node: u32, value: bool) {
+        let mut pending = vec![node];
+        inside[node as usize] = Some(value);
+        while let Some(node) = pending.pop() {
+            for (other, wire) in self.graph.neighbors(node) {
+                let from = if value { other } else { node };
+                let o = other as usize;
+                if self.member[o] && inside[o].is_none() && self.residual(from, wire) {
+                    inside[o] = Some(value);
+                    pending.push(other);
+                }
+            }
+        }
+    }
+
+    // Lists the groups no flow can enter that agree with what was already
+    // decided. Deciding a node never contradicts an earlier decision, so
+    // every branch ends with a cut.
+    fn closed_sets(
+        &self,
+        mut inside: Vec<Option<bool>>,
+        from: usize,
+        cuts: &mut Vec<Cut>,
+    ) -> Result<(), BudgetExceeded> {
+        match (from..inside.len()).find(|n| self.member[*n] && inside[*n].is_none()) {
+            None => {
+                budget::tick(|| format!("listed {} cuts", cuts.len()))?;
+                let side: Vec<u32> = (0..inside.len() as u32)
+                    .filter(|n| inside[*n as usize] == Some(true))
+                    .collect();
+                cuts.push(cut(self.graph, self.member, &side));
+            }
+            Some(node) => {
+                let mut with = inside.clone();
+                self.decide(&mut with, node as u32, true);
+                self.closed_sets(with, node + 1, cuts)?;
+                self.decide(&mut inside, node as u32, false);
+                self.closed_sets(inside, node + 1, cuts)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Graph {
+    /// The smallest cut of the graph. Every component in turn is the sink
+    /// of a maximum flow from the ones before it, which are then merged
+    /// with the source: the first sink on the other side of the smallest
+    /// cut gets a flow of its size, and no flow can be smaller. Flows stop
+    /// as soon as they reach the best cut so far, and the components are
+    /// taken in breadth-first order so that the paths to each sink are
+    /// short, which keeps the search close to linear when the cut is
+    /// small.
+    ///
+    /// A graph of a single component has no cut, its second side is empty.
+    pub fn min_cut(&self) -> Result<Cut, BudgetExceeded> {
+        self.min_cut_among(&vec![true; self.len()])
+    }
+
+    fn min_cut_among(&self, member: &[bool]) -> Result<Cut, BudgetExceeded> {
+        let order = groups(self, member).concat();
+        let Some(&first) = order.first() else {
+            return Ok(cut(self, member, &[]));
+        };
+        let mut flow = Flow::new(self, member);
+        flow.source[first as usize] = true;
+        let mut best: Option<(usize, Vec<u32>)> = None;
+        for (done, &sink) in order.iter().enumerate().skip(1) {
+            budget::tick(|| {
+                format!(
+                    "sent flows to {} of {} components, best cut so far of {} wires",
+                    done,
+                    order.len(),
+                    best.as_ref().map_or(0, |(size, _)| *size)
+                )
+            })?;
+            let cap = best.as_ref().map_or(usize::MAX, |(size, _)| *size);
+            if let (value, Some(side)) = flow.max_flow(sink, cap) {
+                best = Some((value, side));
+            }
+            flow.reset();
+            flow.source[sink as usize] = true;
+        }
+        let side = best.map(|(_, side)| side).unwrap_or_default();
+        Ok(cut(self, member, &side))
+    }
+
+    /// The fewest wires to cut to split the graph, 0 when it already is.
+    pub fn edge_connectivity(&self) -> Result<usize, BudgetExceeded> {
+        Ok(self.min_cut()?.edges.len())
+    }
+
+    /// Every smallest cut of the graph, there are at most `n * (n - 1) / 2`
+    /// of them for `n` components, but exponentially many when the graph
+    /// is already split into many groups.
+    ///
+    /// Each cut is found with the first sink of `min_cut` on its other
+    /// side: its flow is then as small as the cut, and the sides of the
+    /// smallest cuts between the sources and the sink are the groups of
+    /// nodes around the sink that no flow can enter, listed by deciding for
+    /// every node in turn whether it is in the group.
+    pub fn min_cuts(&self) -> Result<Vec<Cut>, BudgetExceeded> {
+        let size = self.edge_connectivity()?;
+        let member = vec![true; self.len()];
+        let order = groups(self, &member).concat();
+        let mut cuts = vec![];
+        let Some(&first) = order.first() else {
+            return Ok(cuts);
+        };
+        let mut flow = Flow::new(self, &member);
+        flow.source[first as usize] = true;
+        for (done, &sink) in order.iter().enumerate().skip(1) {
+            budget::tick(|| {
+                format!(
+                    "listed {} cuts with the sinks of {} of {} components",
+                    cuts.len(),
+                    done,
+                    order.len()
+                )
+            })?;
+            if let (value, Some(_)) = flow.max_flow(sink, size + 1) {
+                if value == size {
+                    let mut inside = vec![None; self.len()];
+                    flow.decide(&mut inside, sink, true);
+                    for node in 0..self.len() as u32 {
+                        if flow.source[node as usize] {
+                            flow.decide(&mut inside, node, false);
+                        }
+                    }
+                    flow.closed_sets(inside, 0, &mut cuts)?;
+                }
+            }
+            flow.reset();
+            flow.source[sink as usize] = true;
+        }
+        Ok(cuts)
+    }
+
+    /// Wires to cut to split the graph into `parts` groups, by cutting the
+    /// group with the smallest cut until there are enough of them. This is
+    /// only an approximation: it is the smallest number of wires for two
+    /// parts, but it may take more than needed for more parts, at worst
+    /// `2 - 2 / parts` times as many. Finding the fewest is NP-hard when
+    /// the number of parts can be anything.
+    pub fn approximate_partition(&self, parts: usize) -> Result<Partition, CutError> {
+        if parts > self.len() {
+            return Err(CutError::TooManyParts {
+                parts,
+                components: self.len(),
+            });
+        }
+        let mut groups: Vec<(Vec<u32>, Option<Cut>)> = groups(self, &vec![true; self.len()])
+            .into_iter()
+            .map(|group| (group, None))
+            .collect();
+        let mut edges = vec![];
+        while groups.len() < parts {
+            for (group, cut) in groups.iter_mut() {
+                if cut.is_none() && group.len() > 1 {
+                    let mut member = vec![false; self.len()];
+                    for &node in group.iter() {
+                        member[node as usize] = true;
+                    }
+                    *cut = Some(self.min_cut_among(&member)?);
+                }
+            }
+            let (ind, _) = groups
+                .iter()
+                .enumerate()
+                .filter_map(|(ind, (_, cut))| Some((ind, cut.as_ref()?.edges.len())))
+                .min_by_key(|(_, size)| *size)
+                .expect("a group of several components is left");
+            let cut = groups.swap_remove(ind).1.expect("the group has a cut");
+            edges.extend(cut.edges);
+            let [first, second] = cut.sides;
+            groups.push((first, None));
+            groups.push((second, None));
+        }
+        let mut parts: Vec<Vec<u32>> = groups.into_iter().map(|(group, _)| group).collect();
+        for part in parts.iter_mut() {
+            part.sort_unstable();
+        }
+        Ok(Partition { edges, parts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day25;
+    use common::Solution;
+
+    // two triangles joined by a single wire
+    const BRIDGED: &str = "a: b c\nb: c\nc: d\nd: e f\ne: f\n";
+
+    fn names(graph: &Graph, nodes: &[u32]) -> String {
+        nodes.iter().map(|n| graph.name(*n)).collect()
+    }
+
+    #[test]
+    fn bridges_and_components() {
+        let graph = Day25::parse(BRIDGED).unwrap();
+        let cut = graph.min_cut().unwrap();
+        let wire = cut.edges[0];
+        assert_eq!(cut.edges.len(), 1);
+        assert_eq!((graph.name(wire.0), graph.name(wire.1)), ("c", "d"));
+        let mut sides = cut.sides.map(|s| names(&graph, &s));
+        sides.sort();
+        assert_eq!(sides, ["abc", "def"]);
+
+        let graph = Day25::parse("a: b\nc: d\n").unwrap();
+        let cut = graph.min_cut().unwrap();
+        assert!(cut.edges.is_empty());
+        assert_eq!(cut.sides.map(|s| names(&graph, &s)), ["ab", "cd"]);
+        assert_eq!(graph.edge_connectivity(), Ok(0));
+        assert_eq!(graph.min_cuts().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn every_min_cut() {
+        // every two wires of a ring split it
+        let ring = Day25::parse("a: b\nb: c\nc: d\nd: e\ne: a\n").unwrap();
+        let cuts = ring.min_cuts().unwrap();
+        assert_eq!(cuts.len(), 10);
+        assert!(cuts.iter().all(|c| c.edges.len() == 2));
+        let mut sides: Vec<[Vec<u32>; 2]> = cuts.into_iter().map(|c| c.sides).collect();
+        sides.sort();
+        sides.dedup();
+        assert_eq!(sides.len(), 10);
+    }
+
+    #[test]
+    fn parts() {
+        let graph = Day25::parse(BRIDGED).unwrap();
+        let halves = graph.approximate_partition(2).unwrap();
+        assert_eq!(halves.edges.len(), 1);
+        let thirds = graph.approximate_partition(3).unwrap();
+        assert_eq!(thirds.edges.len(), 3);
+        let mut sizes = thirds.sizes();
+        sizes.sort();
+        assert_eq!(sizes, [1, 2, 3]);
+        assert_eq!(graph.approximate_partition(1).unwrap().edges, []);
+        assert_eq!(
+            graph.approximate_partition(7),
+            Err(CutError::TooManyParts {
+                parts: 7,
+                components: 6
+            })
+        );
+    }
+
+    #[test]
+    fn greedy_parts() {
+        let graph = Graph::from_wires([
+            ("a", "d"),
+            ("a", "e"),
+            ("a", "g"),
+            ("a", "h"),
+            ("b", "c"),
+            ("b", "d"),
+            ("b", "e"),
+            ("b", "f"),
+            ("b", "h"),
+            ("c", "d"),
+            ("c", "e"),
+            ("c", "f"),
+            ("c", "g"),
+            ("c", "h"),
+            ("d", "g"),
+            ("e", "f"),
+            ("e", "g"),
+            ("e", "h"),
+        ]);
+        // the smallest cut is around f, the only component with three wires,
+        // but once it is cut off the rest takes seven more wires to split
+        // into three groups, while cutting off a, d and g takes nine in all
+        let greedy = graph.approximate_partition(4).unwrap();
+        assert_eq!(greedy.edges.len(), 10);
+        let alone = ["a", "d", "g"].map(|n| graph.id(n).unwrap());
+        let fewest = graph
+            .edges()
+            .iter()
+            .filter(|(x, y)| alone.contains(x) || alone.contains(y))
+            .count();
+        assert_eq!(fewest, 9);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{cut::Cut, Graph};
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+};
+
+/// The fill colours of the components on each side of the cut.
+pub const SIDE_COLORS: [&str; 2] = ["lightblue", "lightsalmon"];
+
+/// The colour of the wires of the cut.
+pub const CUT_COLOR: &str = "red";
+
+// The side of the cut of every component, and whether every wire is cut.
+fn sides(graph: &Graph, cut: &Cut) -> (Vec<usize>, Vec<bool>) {
+    let mut side = vec![0; graph.len()];
+    for &node in &cut.sides[1] {
+        side[node as usize] = 1;
+    }
+    let cut: HashSet<&(u32, u32)> = cut.edges.iter().collect();
+    let cut_wires = graph.edges().iter().map(|w| cut.contains(w)).collect();
+    (side, cut_wires)
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Writes the graph for Graphviz, the components filled with the colour of
+/// their side and the wires of the cut drawn thick in `CUT_COLOR`.
+pub fn write_dot<W: Write>(out: &mut W, graph: &Graph, cut: &Cut) -> io::Result<()> {
+    let (side, cut_wires) = sides(graph, cut);
+    writeln!(out, "graph wiring {{")?;
+    writeln!(out, "    node [style=filled];")?;
+    for node in 0..graph.len() as u32 {
+        writeln!(
+            out,
+            "    {} [fillcolor={}];",
+            dot_id(graph.name(node)),
+            SIDE_COLORS[side[node as usize]]
+        )?;
+    }
+    for (&(a, b), is_cut) in graph.edges().iter().zip(cut_wires) {
+        let style = match is_cut {
+            true => format!(" [color={}, penwidth=3]", CUT_COLOR),
+            false => String::new(),
+        };
+        writeln!(
+            out,
+            "    {} -- {}{};",
+            dot_id(graph.name(a)),
+            dot_id(graph.name(b)),
+            style
+        )?;
+    }
+    writeln!(out, "}}")
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Writes the graph as GraphML, each component with its name, side and
+/// colour, each wire with whether it is cut and its colour.
+pub fn write_graphml<W: Write>(out: &mut W, graph: &Graph, cut: &Cut) -> io::Result<()> {
+    let (side, cut_wires) = sides(graph, cut);
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+    )?;
+    for (id, target, name, kind) in [
+        ("name", "node", "name", "string"),
+        ("side", "node", "side", "int"),
+        ("fill", "node", "color", "string"),
+        ("cut", "edge", "cut", "boolean"),
+        ("stroke", "edge", "color", "string"),
+    ] {
+        writeln!(
+            out,
+            r#"  <key id="{}" for="{}" attr.name="{}" attr.type="{}"/>"#,
+            id, target, name, kind
+        )?;
+    }
+    writeln!(out, r#"  <graph id="wiring" edgedefault="undirected">"#)?;
+    for node in 0..graph.len() as u32 {
+        let side = side[node as usize];
+        writeln!(
+            out,
+            r#"    <node id="n{}"><data key="name">{}</data><data key="side">{}</data><data key="fill">{}</data></node>"#,
+            node,
+            xml_escape(graph.name(node)),
+            side,
+            SIDE_COLORS[side]
+        )?;
+    }
+    for (&(a, b), is_cut) in graph.edges().iter().zip(cut_wires) {
+        writeln!(
+            out,
+            r#"    <edge source="n{}" target="n{}"><data key="cut">{}</data><data key="stroke">{}</data></edge>"#,
+            a,
+            b,
+            is_cut,
+            if is_cut { CUT_COLOR } else { "black" }
+        )?;
+    }
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</graphml>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats() {
+        let graph = Graph::from_wires([("a", "b"), ("b", "c\"&"), ("c\"&", "a"), ("c\"&", "d")]);
+        let cut = graph.min_cut().unwrap();
+
+        let mut dot = vec![];
+        write_dot(&mut dot, &graph, &cut).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.starts_with("graph wiring {\n"));
+        assert!(dot.contains("    \"c\\\"&\" -- \"d\" [color=red, penwidth=3];\n"));
+        assert!(dot.contains("    \"a\" -- \"b\";\n"));
+        assert_eq!(dot.matches("fillcolor=lightsalmon").count(), 1);
+
+        let mut xml = vec![];
+        write_graphml(&mut xml, &graph, &cut).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains(r#"<data key="name">c&quot;&amp;</data>"#));
+        assert_eq!(xml.matches(r#"<data key="cut">true</data>"#).count(), 1);
+        assert_eq!(xml.matches("<node ").count(), 4);
+        assert!(xml.ends_with("</graphml>\n"));
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::{HashMap, HashSet};
+
+/// The wiring diagram. Components are numbered in the order they first
+/// appear, and the wires of each one are stored next to each other.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, u32>,
+    // the neighbours of node `n` are at `offsets[n]..offsets[n + 1]` in
+    // `targets`, connected by the wires at the same positions in `wires`
+    offsets: Vec<u32>,
+    targets: Vec<u32>,
+    wires: Vec<u32>,
+    edges: Vec<(u32, u32)>,
+}
+
+impl Graph {
+    /// The graph of the given wires, each listed once whatever the order of
+    /// its ends. Wires from a component to itself are dropped.
+    pub fn from_wires<'a>(wires: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut graph = Graph::default();
+        let mut seen = HashSet::new();
+        for (a, b) in wires {
+            let (a, b) = (graph.intern(a), graph.intern(b));
+            if a != b && seen.insert((a.min(b), a.max(b))) {
+                graph.edges.push((a.min(b), a.max(b)));
+            }
+        }
+
+        let mut degrees = vec![0_u32; graph.names.len()];
+        for &(a, b) in &graph.edges {
+            degrees[a as usize] += 1;
+            degrees[b as usize] += 1;
+        }
+        graph.offsets = vec![0];
+        for degree in degrees {
+            let last = graph.offsets[graph.offsets.len() - 1];
+            graph.offsets.push(last + degree);
+        }
+        let mut next: Vec<u32> = graph.offsets[..graph.names.len()].to_vec();
+        graph.targets = vec![0; 2 * graph.edges.len()];
+        graph.wires = vec![0; 2 * graph.edges.len()];
+        for (wire, &(a, b)) in graph.edges.iter().enumerate() {
+            for (from, to) in [(a, b), (b, a)] {
+                let slot = next[from as usize] as usize;
+                graph.targets[slot] = to;
+                graph.wires[slot] = wire as u32;
+                next[from as usize] += 1;
+            }
+        }
+        graph
+    }
+
+    fn intern(&mut self, name: &str) -> u32 {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len() as u32;
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    /// The number of components.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, node: u32) -> &str {
+        &self.names[node as usize]
+    }
+
+    pub fn id(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    /// Every wire once, as the ends with the smaller one first, numbered by
+    /// their position.
+    pub fn edges(&self) -> &[(u32, u32)] {
+        &self.edges
+    }
+
+    /// The components wired to `node`, with the wire to each of them.
+    pub fn neighbors(&self, node: u32) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let range = self.offsets[node as usize] as usize..self.offsets[node as usize + 1] as usize;
+        self.targets[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.wires[range].iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interned() {
+        // names that would be the same once concatenated
+        let graph = Graph::from_wires([("ab", "cde"), ("abc", "de"), ("cde", "ab"), ("de", "de")]);
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.edges(), [(0, 1), (2, 3)]);
+        assert_eq!(graph.id("abc"), Some(2));
+        assert_eq!(graph.name(3), "de");
+        assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), [(0, 0)]);
+        assert_eq!(graph.id("xyz"), None);
+    }
+}
@@ -0,0 +1,198 @@
+use anyhow::Result;
+use common::{
+    generate::{self, Generate},
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error, fmt,
+};
+
+pub mod cut;
+pub mod export;
+pub mod graph;
+
+pub use graph::Graph;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingColon(Span),
+    MissingConnections(Span),
+    Empty,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingColon(s) => write!(
+                f,
+                "expected `<component>: <connected components>`, got {}",
+                s
+            ),
+            ParseError::MissingConnections(s) => write!(f, "no connections given for {}", s),
+            ParseError::Empty => write!(f, "there are no components"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+// The product of the sizes of the two groups left by cutting three wires.
+fn split(gr: &Graph) -> Result<usize> {
+    let cut = gr.min_cut()?;
+    if cut.edges.len() != 3 {
+        anyhow::bail!(
+            "the smallest cut has {} wires instead of three",
+            cut.edges.len()
+        );
+    }
+    Ok(cut.sides[0].len() * cut.sides[1].len())
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Input = Graph;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut wires = vec![];
+        for line in Token::lines(input).filter(|l| !l.is_empty()) {
+            let (src, dsts) = line
+                .split_once(":")
+                .ok_or_else(|| ParseError::MissingColon(line.span()))?;
+            let src = src.trim().text();
+            if dsts.words().next().is_none() {
+                return Err(ParseError::MissingConnections(line.span()).into());
+            }
+            wires.extend(dsts.words().map(|dst| (src, dst.text())));
+        }
+        if wires.is_empty() {
+            return Err(ParseError::Empty.into());
+        }
+        Ok(Graph::from_wires(wires))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(split(input)?.into())
+    }
+
+    fn part2(_: &Self::Input) -> Result<Answer> {
+        // there is no puzzle for the second part, the last star is granted
+        // for collecting the other 49 ones
+        Ok(Answer::Text(String::from("*")))
+    }
+}
+
+impl Generate for Day25 {
+    // Two groups of components joined by three wires. Within a group every
+    // component is wired to the two after it around a circle, and then some
+    // more, so that cutting fewer than four wires never splits a group.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let sizes = [size.max(5), rng.gen_range(size.max(5)..=size.max(5) * 2)];
+        let mut names = generate::names(rng, sizes[0] + sizes[1], 3);
+        let second = names.split_off(sizes[0]);
+        let groups = [names, second];
+        let mut wires = BTreeSet::new();
+        let mut connect = |a: &String, b: &String| {
+            if a != b && !wires.contains(&(b.clone(), a.clone())) {
+                wires.insert((a.clone(), b.clone()));
+            }
+        };
+        for group in &groups {
+            let len = group.len();
+            for (ind, name) in group.iter().enumerate() {
+                connect(name, &group[(ind + 1) % len]);
+                connect(name, &group[(ind + 2) % len]);
+                if rng.gen_bool(0.5) {
+                    connect(name, &group[rng.gen_range(0..len)]);
+                }
+            }
+        }
+        let mut cut = BTreeSet::new();
+        while cut.len() < 3 {
+            cut.insert((
+                groups[0][rng.gen_range(0..sizes[0])].clone(),
+                groups[1][rng.gen_range(0..sizes[1])].clone(),
+            ));
+        }
+        for (a, b) in &cut {
+            connect(a, b);
+        }
+        let mut lines: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (a, b) in wires {
+            let (src, dst) = if rng.gen_bool(0.5) { (a, b) } else { (b, a) };
+            lines.entry(src).or_default().push(dst);
+        }
+        let mut lines: Vec<String> = lines
+            .into_iter()
+            .map(|(src, dsts)| format!("{}: {}", src, dsts.join(" ")))
+            .collect();
+        lines.shuffle(rng);
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        generate::{generate, solve_generated},
+        solve, Part,
+    };
+    use std::collections::HashSet;
+
+    const EXAMPLE: &str = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day25>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(54)
+        );
+        let cuts = Day25::parse(EXAMPLE).unwrap().min_cuts().unwrap();
+        assert_eq!(cuts.len(), 1);
+        let mut sizes = cuts[0].sizes();
+        sizes.sort();
+        assert_eq!(sizes, [6, 9]);
+    }
+
+    #[test]
+    fn input_part1() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day25>(input, Part::One).unwrap(),
+            Answer::Number(525264)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day25>(0..10, 8).unwrap();
+        assert_eq!(generate::<Day25>(3, 10), generate::<Day25>(3, 10));
+        // more components than there are three letter names
+        let input = generate::<Day25>(2, 20000);
+        let components: HashSet<&str> = input
+            .split(|c: char| !c.is_ascii_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect();
+        assert!(components.len() >= 40000);
+    }
+}
@@ -0,0 +1,282 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    grid::{Grid, Pos},
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedCharacter(Span),
+    /// The row is not as wide as the first one, `width`.
+    UnevenRow {
+        row: Span,
+        width: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedCharacter(s) => write!(f, "unexpected character {}", s),
+            ParseError::UnevenRow { row, width } => write!(
+                f,
+                "the row {} is {} characters wide instead of {}",
+                row,
+                row.token.chars().count(),
+                width
+            ),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn has_symbol_neighbor(pos: Pos, schematic: &Grid<char>) -> bool {
+    schematic.neighbors8(pos).any(|n| {
+        let ch = schematic[n];
+        ch != '.' && !ch.is_ascii_digit()
+    })
+}
+
+fn collect_starts(
+    pos: Pos,
+    nid: usize,
+    schematic: &Grid<char>,
+    stars: &mut HashMap<Pos, HashSet<usize>>,
+) {
+    for n in schematic.neighbors8(pos) {
+        if schematic[n] == '*' {
+            stars.entry(n).or_default().insert(nid);
+        }
+    }
+}
+
+fn part1(schematic: &Grid<char>) -> u32 {
+    let mut res = 0;
+    let mut cur = 0;
+    let mut good = false;
+    for (l, line) in schematic.rows().enumerate() {
+        if good {
+            res += cur;
+        }
+        cur = 0;
+        good = false;
+        for (i, ch) in line.iter().enumerate() {
+            if let Some(digit) = ch.to_digit(10) {
+                if has_symbol_neighbor(Pos::new(l, i), schematic) {
+                    good = true;
+                }
+                cur = cur * 10 + digit;
+            } else {
+                if good {
+                    res += cur;
+                }
+                cur = 0;
+                good = false;
+            }
+        }
+    }
+    if good {
+        res += cur;
+    }
+    res
+}
+
+fn part2(schematic: &Grid<char>) -> u32 {
+    let mut numbers: Vec<u32> = vec![];
+    let mut stars: HashMap<Pos, HashSet<usize>> = HashMap::new();
+    let mut cur = 0;
+    for (l, line) in schematic.rows().enumerate() {
+        for (i, ch) in line.iter().enumerate() {
+            if let Some(digit) = ch.to_digit(10) {
+                collect_starts(Pos::new(l, i), numbers.len(), schematic, &mut stars);
+                cur = cur * 10 + digit;
+            } else if cur != 0 {
+                numbers.push(cur);
+                cur = 0;
+            }
+        }
+        if cur != 0 {
+            numbers.push(cur);
+            cur = 0;
+        }
+    }
+    let mut res = 0;
+    for nmb in stars.values() {
+        if nmb.len() == 2 {
+            res += nmb.iter().map(|ind| numbers[*ind]).product::<u32>()
+        }
+    }
+    res
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Grid<char>;
+
+    // Besides the digits of the part numbers and the dots, every ASCII
+    // punctuation character is a symbol.
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut rows: Vec<Vec<char>> = vec![];
+        for line in Token::lines(input).filter(|l| !l.is_empty()) {
+            let row = line
+                .chars()
+                .map(|t| match t.char() {
+                    c if c.is_ascii_digit() || c.is_ascii_punctuation() => Ok(c),
+                    _ => Err(ParseError::UnexpectedCharacter(t.span())),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    return Err(ParseError::UnevenRow {
+                        row: line.span(),
+                        width: first.len(),
+                    }
+                    .into());
+                }
+            }
+            rows.push(row);
+        }
+        Grid::from_rows(rows)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
+const SYMBOLS: &[u8] = b"*#+$/@%=&-";
+
+impl Generate for Day3 {
+    // Part numbers never touch each other on the same row, otherwise they
+    // would read as a single number.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let side = size.max(3);
+        let mut r = String::new();
+        for _ in 0..side {
+            let mut row = String::new();
+            while row.len() < side {
+                match rng.gen_range(0..10) {
+                    0..=1 => {
+                        let number = rng.gen_range(1..1000).to_string();
+                        if row.len() + number.len() <= side {
+                            row += &number;
+                        }
+                        if row.len() < side {
+                            row.push('.');
+                        }
+                    }
+                    2 => row.push(SYMBOLS[rng.gen_range(0..SYMBOLS.len())] as char),
+                    _ => row.push('.'),
+                }
+            }
+            r += &row;
+            r.push('\n');
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn parse_errors() {
+        let err = |input: &str| {
+            Day3::parse(input)
+                .err()
+                .and_then(|e| e.downcast::<ParseError>().ok())
+        };
+        assert_eq!(
+            err("467..\n\n...*\n"),
+            Some(ParseError::UnevenRow {
+                row: Span {
+                    line: 3,
+                    column: 1,
+                    token: "...*".to_string()
+                },
+                width: 5
+            })
+        );
+        assert_eq!(
+            err("467..\n...*.\n...*..\n").unwrap().to_string(),
+            "the row `...*..` at line 3, column 1 is 6 characters wide instead of 5"
+        );
+        assert_eq!(
+            err("467..\n..a*.\n"),
+            Some(ParseError::UnexpectedCharacter(Span {
+                line: 2,
+                column: 3,
+                token: "a".to_string()
+            }))
+        );
+        assert_eq!(
+            err("46 7.\n"),
+            Some(ParseError::UnexpectedCharacter(Span {
+                line: 1,
+                column: 3,
+                token: " ".to_string()
+            }))
+        );
+        assert_eq!(err(EXAMPLE), None);
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day3>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(4361)
+        );
+        assert_eq!(
+            solve::<Day3>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(467835)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day3>(input, Part::One).unwrap(),
+            Answer::Number(544664)
+        );
+        assert_eq!(
+            solve::<Day3>(input, Part::Two).unwrap(),
+            Answer::Number(84495585)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day3>(0..10, 40).unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{cmp, collections::HashSet, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingColon(Span),
+    MissingBar(Span),
+    InvalidNumber(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingColon(s) => write!(f, "expected `Card <id>:` in {}", s),
+            ParseError::MissingBar(s) => write!(f, "expected `|` between the numbers in {}", s),
+            ParseError::InvalidNumber(s) => write!(f, "invalid number {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+pub struct Card {
+    winning: HashSet<usize>,
+    numbers: Vec<usize>,
+}
+
+fn numbers<B: FromIterator<usize>>(list: Token) -> Result<B, ParseError> {
+    list.words()
+        .map(|v| v.parse().map_err(ParseError::InvalidNumber))
+        .collect()
+}
+
+fn card(line: Token) -> Result<Card, ParseError> {
+    let (_, content) = line
+        .split_once(":")
+        .ok_or_else(|| ParseError::MissingColon(line.span()))?;
+    let (winning, owned) = content
+        .split_once("|")
+        .ok_or_else(|| ParseError::MissingBar(content.span()))?;
+    Ok(Card {
+        winning: numbers(winning)?,
+        numbers: numbers(owned)?,
+    })
+}
+
+fn count_wins(card: &Card) -> usize {
+    card.numbers
+        .iter()
+        .filter(|v| card.winning.contains(v))
+        .count()
+}
+
+fn part1(cards: &[Card]) -> usize {
+    let mut total = 0;
+    for card in cards {
+        let won = count_wins(card);
+        if won > 0 {
+            total += 1 << (won - 1);
+        }
+    }
+    total
+}
+
+fn part2(cards: &[Card]) -> usize {
+    let mut obtained = vec![1; cards.len()];
+    for i in 0..obtained.len() {
+        let won = count_wins(&cards[i]);
+        for u in i + 1..=cmp::min(i + won, obtained.len() - 1) {
+            obtained[u] += obtained[i];
+        }
+    }
+    obtained.iter().sum()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Token::lines(input)
+            .filter(|l| !l.is_empty())
+            .map(card)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
+}
+
+impl Generate for Day4 {
+    // Cards never win copies past the end of the table, and cards that are
+    // already held many times win nothing, so that the count stays small.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let count = size.max(1);
+        let mut obtained = vec![1_usize; count];
+        let mut r = String::new();
+        for id in 0..count {
+            let mut values: Vec<usize> = (1..100).collect();
+            values.shuffle(rng);
+            let (winning, others) = values.split_at(10);
+            let won = if obtained[id] > 10_000 {
+                0
+            } else {
+                rng.gen_range(0..=cmp::min(10, count - id - 1))
+            };
+            for later in id + 1..=id + won {
+                obtained[later] += obtained[id];
+            }
+            let mut numbers: Vec<usize> = winning[..won]
+                .iter()
+                .chain(&others[..25 - won])
+                .copied()
+                .collect();
+            numbers.shuffle(rng);
+            let list = |values: &[usize]| {
+                values
+                    .iter()
+                    .map(|v| format!("{:2}", v))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            r += &format!(
+                "Card {:3}: {} | {}\n",
+                id + 1,
+                list(winning),
+                list(&numbers)
+            );
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day4>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(13)
+        );
+        assert_eq!(
+            solve::<Day4>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(30)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day4>(input, Part::One).unwrap(),
+            Answer::Number(21158)
+        );
+        assert_eq!(
+            solve::<Day4>(input, Part::Two).unwrap(),
+            Answer::Number(6050769)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day4>(0..10, 200).unwrap();
+    }
+}
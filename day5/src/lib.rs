@@ -0,0 +1,364 @@
+use anyhow::{Context, Result};
+use common::{
+    generate::Generate,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{cmp, error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingSeeds(Span),
+    InvalidNumber(Span),
+    InvalidMapping(Span),
+    MissingHeader(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingSeeds(s) => write!(f, "expected `seeds:` instead of {}", s),
+            ParseError::InvalidNumber(s) => write!(f, "invalid number {}", s),
+            ParseError::InvalidMapping(s) => {
+                write!(f, "expected destination, source and length in {}", s)
+            }
+            ParseError::MissingHeader(s) => write!(f, "mapping {} before any map header", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Debug)]
+struct Range {
+    start: usize,
+    length: usize,
+}
+
+#[derive(Debug)]
+struct Mapping {
+    source: usize,
+    dest: usize,
+    length: usize,
+}
+
+#[derive(Debug)]
+struct MappingResult {
+    mapped: Range,
+    remaining: Vec<Range>,
+}
+
+impl Mapping {
+    fn map(&self, value: usize) -> Option<usize> {
+        if (self.source..self.source + self.length).contains(&value) {
+            return Some(self.dest + (value - self.source));
+        }
+        None
+    }
+
+    fn map_range(&self, range: &Range) -> Option<MappingResult> {
+        let start = cmp::max(self.source, range.start);
+        let end = cmp::min(self.source + self.length, range.start + range.length);
+        if start >= end {
+            return None;
+        }
+        let mut result = MappingResult {
+            mapped: Range {
+                start: self.dest + (start - self.source),
+                length: end - start,
+            },
+            remaining: vec![],
+        };
+        if range.start < start {
+            result.remaining.push(Range {
+                start: range.start,
+                length: start - range.start,
+            });
+        }
+        if range.start + range.length > end {
+            result.remaining.push(Range {
+                start: end,
+                length: range.start + range.length - end,
+            });
+        }
+        Some(result)
+    }
+}
+
+fn part1(seeds: &[usize], mappings: &[Vec<Mapping>]) -> Option<usize> {
+    let mut result: Option<usize> = None;
+    for seed in seeds {
+        let mut mapped = *seed;
+        for m in mappings {
+            mapped = m.iter().find_map(|m| m.map(mapped)).unwrap_or(mapped);
+        }
+        if result.is_none() || result.unwrap() > mapped {
+            result = Some(mapped);
+        }
+    }
+    result
+}
+
+fn part2(seeds: &[usize], mappings: &[Vec<Mapping>]) -> Option<usize> {
+    let mut ranges: Vec<Range> = seeds
+        .windows(2)
+        .step_by(2)
+        .map(|win| Range {
+            start: win[0],
+            length: win[1],
+        })
+        .filter(|r| r.length > 0)
+        .collect();
+    for m in mappings {
+        let mut mapped: Vec<Range> = vec![];
+        while let Some(range) = ranges.pop() {
+            if let Some(mut mapping_result) = m.iter().find_map(|m| m.map_range(&range)) {
+                mapped.push(mapping_result.mapped);
+                ranges.append(&mut mapping_result.remaining);
+            } else {
+                mapped.push(range);
+            }
+        }
+        ranges = mapped;
+    }
+    ranges.iter().map(|r| r.start).min()
+}
+
+pub struct Almanac {
+    seeds: Vec<usize>,
+    mappings: Vec<Vec<Mapping>>,
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = Token::lines(input).filter(|l| !l.is_empty());
+        let first = lines.next().ok_or_else(|| {
+            ParseError::MissingSeeds(Span {
+                line: 1,
+                column: 1,
+                token: String::new(),
+            })
+        })?;
+        let seeds = first
+            .strip_prefix("seeds:")
+            .ok_or_else(|| ParseError::MissingSeeds(first.span()))?;
+        let seeds: Vec<usize> = seeds
+            .words()
+            .map(|s| s.parse().map_err(ParseError::InvalidNumber))
+            .collect::<Result<_, _>>()?;
+        let mut mappings: Vec<Vec<Mapping>> = vec![];
+        for line in lines {
+            if line.text().ends_with("map:") {
+                mappings.push(vec![]);
+                continue;
+            }
+            let values: Vec<usize> = line
+                .words()
+                .map(|s| s.parse().map_err(ParseError::InvalidNumber))
+                .collect::<Result<_, _>>()?;
+            if values.len() != 3 {
+                return Err(ParseError::InvalidMapping(line.span()).into());
+            }
+            mappings
+                .last_mut()
+                .ok_or_else(|| ParseError::MissingHeader(line.span()))?
+                .push(Mapping {
+                    source: values[1],
+                    dest: values[0],
+                    length: values[2],
+                });
+        }
+        Ok(Almanac { seeds, mappings })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(part1(&input.seeds, &input.mappings)
+            .context("no seeds to map")?
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(part2(&input.seeds, &input.mappings)
+            .context("no seed ranges to map")?
+            .into())
+    }
+}
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+const SPAN: usize = 1 << 32;
+
+impl Generate for Day5 {
+    // Every map cuts the numbers into `size` pieces and moves them around,
+    // leaving some pieces where they are.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let seeds: Vec<String> = (0..10)
+            .map(|ind| match ind % 2 {
+                0 => rng.gen_range(0..SPAN / 2),
+                _ => rng.gen_range(1..SPAN / 20),
+            })
+            .map(|v| v.to_string())
+            .collect();
+        let mut r = format!("seeds: {}\n", seeds.join(" "));
+        for names in CATEGORIES.windows(2) {
+            let mut cuts: Vec<usize> = (0..size.max(1)).map(|_| rng.gen_range(0..SPAN)).collect();
+            cuts.extend([0, SPAN]);
+            cuts.sort();
+            cuts.dedup();
+            let mut pieces: Vec<Range> = cuts
+                .windows(2)
+                .map(|w| Range {
+                    start: w[0],
+                    length: w[1] - w[0],
+                })
+                .collect();
+            pieces.shuffle(rng);
+            let mut dest = 0;
+            let mut lines = vec![];
+            for piece in pieces {
+                if rng.gen_bool(0.8) {
+                    lines.push(format!("{} {} {}", dest, piece.start, piece.length));
+                }
+                dest += piece.length;
+            }
+            r += &format!(
+                "\n{}-to-{} map:\n{}\n",
+                names[0],
+                names[1],
+                lines.join("\n")
+            );
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day5>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(35)
+        );
+        assert_eq!(
+            solve::<Day5>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(46)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day5>(input, Part::One).unwrap(),
+            Answer::Number(289863851)
+        );
+        assert_eq!(
+            solve::<Day5>(input, Part::Two).unwrap(),
+            Answer::Number(60568880)
+        );
+    }
+
+    // Maps the seeds of the ranges one by one.
+    fn part2_naive(seeds: &[usize], mappings: &[Vec<Mapping>]) -> Option<usize> {
+        let seeds: Vec<usize> = seeds
+            .chunks_exact(2)
+            .flat_map(|range| range[0]..range[0] + range[1])
+            .collect();
+        part1(&seeds, mappings)
+    }
+
+    // Maps with disjoint source ranges in random order.
+    fn mappings() -> impl Strategy<Value = Vec<Vec<Mapping>>> {
+        let map = prop::collection::vec((0..5_usize, 0..8_usize, 0..80_usize), 0..5)
+            .prop_map(|raw| {
+                let mut source = 0;
+                raw.into_iter()
+                    .map(|(gap, length, dest)| {
+                        source += gap;
+                        let mapping = Mapping {
+                            source,
+                            dest,
+                            length,
+                        };
+                        source += length;
+                        mapping
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .prop_shuffle();
+        prop::collection::vec(map, 1..5)
+    }
+
+    proptest! {
+        #[test]
+        fn ranges_match_seeds(
+            ranges in prop::collection::vec((0..60_usize, 0..10_usize), 1..4),
+            mappings in mappings(),
+        ) {
+            let seeds: Vec<usize> = ranges.iter().flat_map(|&(s, l)| [s, l]).collect();
+            prop_assert_eq!(part2(&seeds, &mappings), part2_naive(&seeds, &mappings));
+        }
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day5>(0..10, 30).unwrap();
+    }
+}
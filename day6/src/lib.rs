@@ -0,0 +1,181 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingLine(&'static str),
+    InvalidNumber(Span),
+    CountMismatch { times: usize, distances: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingLine(title) => write!(f, "missing {} line", title),
+            ParseError::InvalidNumber(s) => write!(f, "invalid number {}", s),
+            ParseError::CountMismatch { times, distances } => {
+                write!(f, "{} times do not match {} distances", times, distances)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn game(t: i64, d: i64) -> i64 {
+    // (t - dt) * dt > d => dt^2 - dt * t + d < 0
+    // dt1, dt2 = t/2 +/- sqrt(t^2/4 - d)
+    // The winning hold times are symmetric around t/2, so only the first one
+    // is needed. The float estimate of it is nudged to the exact value.
+    let wins = |dt: i64| (t - dt) * dt > d;
+    if !wins(t / 2) {
+        return 0;
+    }
+    let ft = t as f64;
+    let fd = d as f64;
+    let dt1 = ft / 2.0_f64 - ((ft * ft) / 4.0_f64 - fd).sqrt();
+    let mut first = dt1.floor().max(0.0) as i64;
+    while first > 0 && wins(first - 1) {
+        first -= 1;
+    }
+    while !wins(first) {
+        first += 1;
+    }
+    t - 2 * first + 1
+}
+
+fn read_numbers(input: &str, title: &'static str) -> Result<Vec<i64>, ParseError> {
+    let line = Token::lines(input)
+        .find_map(|l| l.strip_prefix(title))
+        .ok_or(ParseError::MissingLine(title))?;
+    line.words()
+        .map(|s| s.parse().map_err(ParseError::InvalidNumber))
+        .collect()
+}
+
+// The numbers of the second part are the same ones with the spaces between
+// them ignored.
+fn join(numbers: &[i64]) -> Result<i64> {
+    Ok(numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<String>()
+        .parse()?)
+}
+
+pub struct Races {
+    times: Vec<i64>,
+    distances: Vec<i64>,
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Races;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let times = read_numbers(input, "Time:")?;
+        let distances = read_numbers(input, "Distance:")?;
+        if times.len() != distances.len() {
+            return Err(ParseError::CountMismatch {
+                times: times.len(),
+                distances: distances.len(),
+            }
+            .into());
+        }
+        Ok(Races { times, distances })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(input
+            .times
+            .iter()
+            .zip(input.distances.iter())
+            .map(|(t, d)| game(*t, *d))
+            .product::<i64>()
+            .into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(game(join(&input.times)?, join(&input.distances)?).into())
+    }
+}
+
+impl Generate for Day6 {
+    // At most four races with two digit times, so that the joined numbers of
+    // the second part still fit.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let races: Vec<(i64, i64)> = (0..size.clamp(1, 4))
+            .map(|_| {
+                let t = rng.gen_range(10..100);
+                (t, rng.gen_range(t * t / 8..t * t / 4))
+            })
+            .collect();
+        let times: String = races.iter().map(|(t, _)| format!("{:5}", t)).collect();
+        let distances: String = races.iter().map(|(_, d)| format!("{:5}", d)).collect();
+        format!("Time:    {}\nDistance:{}\n", times, distances)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day6>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(288)
+        );
+        assert_eq!(
+            solve::<Day6>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(71503)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day6>(input, Part::One).unwrap(),
+            Answer::Number(1413720)
+        );
+        assert_eq!(
+            solve::<Day6>(input, Part::Two).unwrap(),
+            Answer::Number(30565288)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day6>(0..10, 4).unwrap();
+    }
+
+    fn game_naive(t: i64, d: i64) -> i64 {
+        (0..=t).filter(|dt| (t - dt) * dt > d).count() as i64
+    }
+
+    proptest! {
+        #[test]
+        fn game_matches_naive(
+            (t, d) in (0..500_i64).prop_flat_map(|t| (Just(t), 0..=t * t / 4 + 2)),
+        ) {
+            prop_assert_eq!(game(t, d), game_naive(t, d));
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidHand(Span),
+    UnknownCard(Span),
+    MissingBid(Span),
+    InvalidBid(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidHand(s) => write!(f, "expected a hand of five cards, got {}", s),
+            ParseError::UnknownCard(s) => write!(f, "unknown card {}", s),
+            ParseError::MissingBid(s) => write!(f, "missing bid after {}", s),
+            ParseError::InvalidBid(s) => write!(f, "invalid bid {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+const CARDS_ORDER: &str = "AKQJT98765432";
+
+#[derive(Debug, Clone)]
+pub struct Hand {
+    cards: String,
+    bid: usize,
+}
+
+impl Hand {
+    // Calculates the rank of a hand: five of a kind = 0, ..., high card = 6
+    fn rank(&self, joker: char) -> u8 {
+        let jokers = self.cards.chars().filter(|c| *c == joker).count();
+        let mut kinds: HashMap<char, usize> = HashMap::new();
+        self.cards
+            .chars()
+            .filter(|c| *c != joker)
+            .for_each(|c| *kinds.entry(c).or_insert(0) += 1);
+        let len = kinds.len();
+        let longest = *kinds.values().max().unwrap_or(&0);
+        match (len, longest + jokers) {
+            (0, _) => 0, // all jokers == five of a kind
+            (1, _) => 0, // five of a kind
+            (2, 4) => 1, // four of a kind
+            (2, _) => 2, // full house
+            (3, 3) => 3, // three of a kind
+            (3, _) => 4, // two pairs
+            (4, _) => 5, // a pair
+            _ => 6,      // high card
+        }
+    }
+
+    // Calculates a weight of a hand based on individual cards:
+    // such as the stronger card has a less weight
+    fn weight(&self, joker: char, ordering: &str) -> usize {
+        self.cards
+            .chars()
+            .map(|c| {
+                if c == joker {
+                    ordering.len()
+                } else {
+                    ordering.find(c).unwrap()
+                }
+            })
+            .fold(0, |a, v| a * (ordering.len() + 1) + v)
+    }
+}
+
+fn comparator(joker: char) -> impl for<'a, 'b> Fn(&'a Hand, &'b Hand) -> std::cmp::Ordering {
+    move |lhs: &Hand, rhs: &Hand| -> Ordering {
+        lhs.rank(joker).cmp(&rhs.rank(joker)).then_with(|| {
+            lhs.weight(joker, CARDS_ORDER)
+                .cmp(&rhs.weight(joker, CARDS_ORDER))
+        })
+    }
+}
+
+fn accumulate_bids(hands: &[Hand]) -> usize {
+    hands
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (ind, hand)| acc + (hands.len() - ind) * hand.bid)
+}
+
+fn winnings(hands: &[Hand], joker: char) -> usize {
+    let mut hands = hands.to_vec();
+    hands.sort_by(comparator(joker));
+    accumulate_bids(&hands)
+}
+
+fn hand(line: Token) -> Result<Hand, ParseError> {
+    let mut parts = line.words();
+    let cards = parts
+        .next()
+        .ok_or_else(|| ParseError::InvalidHand(line.span()))?;
+    if cards.text().chars().count() != 5 {
+        return Err(ParseError::InvalidHand(cards.span()));
+    }
+    if let Some(card) = cards.chars().find(|c| !CARDS_ORDER.contains(c.text())) {
+        return Err(ParseError::UnknownCard(card.span()));
+    }
+    let bid = parts
+        .next()
+        .ok_or_else(|| ParseError::MissingBid(cards.span()))?;
+    Ok(Hand {
+        cards: cards.text().to_owned(),
+        bid: bid.parse().map_err(ParseError::InvalidBid)?,
+    })
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<Hand>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Token::lines(input)
+            .filter(|l| !l.is_empty())
+            .map(hand)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(winnings(input, ' ').into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(winnings(input, 'J').into())
+    }
+}
+
+impl Generate for Day7 {
+    // The hands are all different, so that no two of them tie.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let cards: Vec<char> = CARDS_ORDER.chars().collect();
+        let mut hands = HashSet::new();
+        let mut r = String::new();
+        while hands.len() < size.max(1) {
+            let hand: String = (0..5).map(|_| *cards.choose(rng).unwrap()).collect();
+            if hands.insert(hand.clone()) {
+                r += &format!("{} {}\n", hand, rng.gen_range(1..=1000));
+            }
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day7>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(6440)
+        );
+        assert_eq!(
+            solve::<Day7>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(5905)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day7>(input, Part::One).unwrap(),
+            Answer::Number(251287184)
+        );
+        assert_eq!(
+            solve::<Day7>(input, Part::Two).unwrap(),
+            Answer::Number(250757288)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day7>(0..10, 300).unwrap();
+    }
+}
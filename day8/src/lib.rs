@@ -0,0 +1,395 @@
+use anyhow::{anyhow, bail, Result};
+use common::{
+    budget,
+    generate::Generate,
+    numtheory::{self, NumberTheoryError},
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingInstructions,
+    InvalidInstruction(Span),
+    InvalidNode(Span),
+    UnknownNode(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingInstructions => write!(f, "missing the instructions line"),
+            ParseError::InvalidInstruction(s) => write!(f, "expected L or R instead of {}", s),
+            ParseError::InvalidNode(s) => write!(f, "expected `AAA = (BBB, CCC)`, got {}", s),
+            ParseError::UnknownNode(s) => write!(f, "node {} is not defined", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn step<'a>(start: &str, dir: char, paths: &'a HashMap<String, (String, String)>) -> &'a str {
+    let dsts = paths.get(start).unwrap();
+    if dir == 'L' {
+        return &dsts.0;
+    }
+    &dsts.1
+}
+
+/// The steps at which a ghost stands on a Z-node: the ones in `prefix`
+/// before it enters its cycle, then `cycle_start + hit + k * period` for
+/// every `hit` in `hits`.
+struct Orbit {
+    prefix: Vec<i128>,
+    cycle_start: i128,
+    period: i128,
+    hits: Vec<i128>,
+}
+
+impl Orbit {
+    fn trace(from: &str, network: &Network) -> Self {
+        let dirs: Vec<char> = network.instructions.chars().collect();
+        let mut seen: HashMap<(&str, usize), i128> = HashMap::new();
+        let mut z_nodes = vec![];
+        let mut location = from;
+        let mut steps = 0_i128;
+        loop {
+            let state = (location, steps as usize % dirs.len());
+            if let Some(&cycle_start) = seen.get(&state) {
+                let (prefix, hits) = z_nodes.iter().partition(|t| **t < cycle_start);
+                return Orbit {
+                    prefix,
+                    cycle_start,
+                    period: steps - cycle_start,
+                    hits: hits.iter().map(|t| t - cycle_start).collect(),
+                };
+            }
+            seen.insert(state, steps);
+            if location.ends_with('Z') {
+                z_nodes.push(steps);
+            }
+            location = step(location, dirs[state.1], &network.paths);
+            steps += 1;
+        }
+    }
+
+    fn at_z_node(&self, steps: i128) -> bool {
+        if steps < self.cycle_start {
+            self.prefix.contains(&steps)
+        } else {
+            self.hits
+                .contains(&((steps - self.cycle_start) % self.period))
+        }
+    }
+}
+
+pub struct Network {
+    instructions: String,
+    paths: HashMap<String, (String, String)>,
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Network;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = Token::lines(input).filter(|l| !l.is_empty());
+        let instructions = lines.next().ok_or(ParseError::MissingInstructions)?;
+        if let Some(dir) = instructions
+            .chars()
+            .find(|c| !matches!(c.char(), 'L' | 'R'))
+        {
+            return Err(ParseError::InvalidInstruction(dir.span()).into());
+        }
+        let mut paths: HashMap<String, (String, String)> = HashMap::new();
+        let mut targets = vec![];
+        for line in lines {
+            let (src, dsts) = line
+                .split_once("=")
+                .ok_or_else(|| ParseError::InvalidNode(line.span()))?;
+            let (left, right) = dsts
+                .trim()
+                .strip_prefix("(")
+                .and_then(|d| d.strip_suffix(")"))
+                .and_then(|d| d.split_once(","))
+                .ok_or_else(|| ParseError::InvalidNode(dsts.span()))?;
+            let (left, right) = (left.trim(), right.trim());
+            paths.insert(
+                src.trim().text().to_owned(),
+                (left.text().to_owned(), right.text().to_owned()),
+            );
+            targets.extend([left, right]);
+        }
+        if let Some(unknown) = targets.iter().find(|t| !paths.contains_key(t.text())) {
+            return Err(ParseError::UnknownNode(unknown.span()).into());
+        }
+        Ok(Network {
+            instructions: instructions.text().to_string(),
+            paths,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        if !input.paths.contains_key("AAA") {
+            bail!("there is no AAA node to start from");
+        }
+        // the walk is a cycle once it is back on a node at the same
+        // instruction
+        let dirs: Vec<char> = input.instructions.chars().collect();
+        let mut seen = HashSet::new();
+        let mut steps = 0;
+        let mut current = "AAA";
+        while current != "ZZZ" {
+            budget::tick(|| format!("walked {} steps from AAA without reaching ZZZ", steps))?;
+            let state = (current, steps % dirs.len());
+            if !seen.insert(state) {
+                bail!(
+                    "ZZZ cannot be reached from AAA, the walk repeats itself after {} steps",
+                    steps
+                );
+            }
+            current = step(current, dirs[state.1], &input.paths);
+            steps += 1;
+        }
+        Ok(steps.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        /*
+         * We cannot brute force the part 2 as there are tooooo many steps.
+         * Each ghost walks a finite state machine (node, instruction index),
+         * so it ends up in a cycle and its Z-node visits form arithmetic
+         * progressions. All ghosts meet at Z-nodes either before some of
+         * them entered their cycles (checked directly) or at a solution of
+         * the congruences picking one Z-node visit from every cycle.
+         */
+        let orbits: Vec<Orbit> = input
+            .paths
+            .keys()
+            .filter(|k| k.ends_with('A'))
+            .map(|g| Orbit::trace(g, input))
+            .collect();
+        if orbits.is_empty() {
+            bail!("there are no ghosts starting at A-nodes");
+        }
+        let early = orbits
+            .iter()
+            .flat_map(|o| o.prefix.iter().copied())
+            .filter(|t| orbits.iter().all(|o| o.at_z_node(*t)))
+            .min();
+        if let Some(steps) = early {
+            return Ok(steps.into());
+        }
+
+        let cycle_start = orbits.iter().map(|o| o.cycle_start).max().unwrap();
+        // every combination of one Z-node visit per cycle, enumerated like an
+        // odometer as there can be too many to keep them all at once
+        if orbits.iter().any(|o| o.hits.is_empty()) {
+            bail!("the ghosts never stand on Z-nodes at the same time");
+        }
+        let combinations = orbits
+            .iter()
+            .fold(1_u128, |acc, o| acc.saturating_mul(o.hits.len() as u128));
+        let mut picks = vec![0; orbits.len()];
+        let mut tried = 0_u128;
+        let mut best: Option<i128> = None;
+        loop {
+            budget::tick(|| {
+                format!(
+                    "tried {} of {} combinations of Z-node visits",
+                    tried, combinations
+                )
+            })?;
+            let congruences: Vec<(i128, i128)> = orbits
+                .iter()
+                .zip(&picks)
+                .map(|(o, &pick)| (o.cycle_start + o.hits[pick], o.period))
+                .collect();
+            match numtheory::crt_at_least(&congruences, cycle_start) {
+                Ok(steps) => best = Some(best.map_or(steps, |b| b.min(steps))),
+                Err(NumberTheoryError::NoSolution) => {}
+                Err(e) => return Err(e.into()),
+            }
+            tried += 1;
+            let Some(ind) = (0..picks.len()).find(|&ind| picks[ind] + 1 < orbits[ind].hits.len())
+            else {
+                break;
+            };
+            picks[ind] += 1;
+            picks[..ind].fill(0);
+        }
+        best.map(Answer::from)
+            .ok_or_else(|| anyhow!("the ghosts never stand on Z-nodes at the same time"))
+    }
+}
+
+// A random node name ending with neither A nor Z.
+fn node_name<R: Rng>(rng: &mut R) -> String {
+    let mut name: String = (0..2).map(|_| rng.gen_range('A'..='Z')).collect();
+    name.push(rng.gen_range('B'..='Y'));
+    name
+}
+
+const PRIMES: [usize; 8] = [3, 5, 7, 11, 13, 17, 19, 23];
+
+impl Generate for Day8 {
+    // Every ghost walks its own loop of nodes from the A-node to the Z-node
+    // and then back to the node after the A-node, whichever way it turns.
+    // The loops are the length of the instructions times distinct primes, so
+    // a ghost only ever passes its Z-node at the same instruction. The first
+    // ghost walks from AAA to ZZZ. Some unreachable nodes are mixed in too.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let instructions: String = (0..rng.gen_range(5..20))
+            .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+            .collect();
+        let mut names = HashSet::new();
+        let mut fresh = |rng: &mut R| loop {
+            let name = node_name(rng);
+            if names.insert(name.clone()) {
+                return name;
+            }
+        };
+        let mut nodes: Vec<(String, String, String)> = vec![];
+        let mut ends = HashSet::new();
+        let mut primes = PRIMES;
+        primes.shuffle(rng);
+        for (ghost, prime) in primes.into_iter().take(size.clamp(1, 6)).enumerate() {
+            let (start, end) = if ghost == 0 {
+                (String::from("AAA"), String::from("ZZZ"))
+            } else {
+                loop {
+                    let prefix: String = (0..2).map(|_| rng.gen_range('A'..='Z')).collect();
+                    if ends.insert(prefix.clone()) && prefix != "AA" && prefix != "ZZ" {
+                        break (prefix.clone() + "A", prefix + "Z");
+                    }
+                }
+            };
+            let mut chain = vec![start];
+            chain.extend((1..instructions.len() * prime).map(|_| fresh(rng)));
+            chain.push(end);
+            for pair in chain.windows(2) {
+                nodes.push((pair[0].clone(), pair[1].clone(), pair[1].clone()));
+            }
+            nodes.push((
+                chain[chain.len() - 1].clone(),
+                chain[1].clone(),
+                chain[1].clone(),
+            ));
+        }
+        let known: Vec<String> = nodes.iter().map(|n| n.0.clone()).collect();
+        for _ in 0..rng.gen_range(0..20) {
+            let name = fresh(rng);
+            let left = known.choose(rng).unwrap().clone();
+            let right = known.choose(rng).unwrap().clone();
+            nodes.push((name, left, right));
+        }
+        nodes.shuffle(rng);
+        let mut r = instructions + "\n\n";
+        for (name, left, right) in nodes {
+            r += &format!("{} = ({}, {})\n", name, left, right);
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        budget::{with_budget, Budget, BudgetExceeded},
+        generate::solve_generated,
+        solve, Part,
+    };
+
+    const EXAMPLE1: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE2: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE3: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day8>(EXAMPLE1, Part::One).unwrap(),
+            Answer::Number(2)
+        );
+        assert_eq!(
+            solve::<Day8>(EXAMPLE2, Part::One).unwrap(),
+            Answer::Number(6)
+        );
+        assert_eq!(
+            solve::<Day8>(EXAMPLE3, Part::Two).unwrap(),
+            Answer::Number(6)
+        );
+    }
+
+    #[test]
+    fn unreachable_zzz() {
+        let input = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+        let err = solve::<Day8>(input, Part::One).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "ZZZ cannot be reached from AAA, the walk repeats itself after 2 steps"
+        );
+        let budget = Budget {
+            time: None,
+            steps: Some(3),
+        };
+        let err = with_budget(budget, || solve::<Day8>(EXAMPLE2, Part::One)).unwrap_err();
+        let err = err.downcast_ref::<BudgetExceeded>().unwrap();
+        assert_eq!(err.progress, "walked 3 steps from AAA without reaching ZZZ");
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day8>(input, Part::One).unwrap(),
+            Answer::Number(19199)
+        );
+        assert_eq!(
+            solve::<Day8>(input, Part::Two).unwrap(),
+            Answer::Number(13663968099527)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day8>(0..10, 6).unwrap();
+    }
+}
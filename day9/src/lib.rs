@@ -0,0 +1,137 @@
+use anyhow::Result;
+use common::{
+    generate::Generate,
+    parse::{Span, Token},
+    Answer, Solution,
+};
+use rand::Rng;
+use std::{error, fmt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidNumber(Span),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidNumber(s) => write!(f, "invalid number {}", s),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn diff(seq: &[i32]) -> Vec<i32> {
+    seq.iter()
+        .zip(seq.iter().skip(1))
+        .map(|(a, b)| b - a)
+        .collect()
+}
+
+fn extrapolate(seq: &[i32]) -> i32 {
+    if seq.len() == 1 {
+        return seq[0];
+    }
+    seq.last().unwrap() + extrapolate(&diff(seq))
+}
+
+fn extrapolate_left(seq: &[i32]) -> i32 {
+    if seq.len() == 1 {
+        return seq[0];
+    }
+    seq[0] - extrapolate_left(&diff(seq))
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Token::lines(input)
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                l.words()
+                    .map(|s| s.parse().map_err(ParseError::InvalidNumber))
+                    .collect::<Result<Vec<i32>, _>>()
+            })
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(input.iter().map(|v| extrapolate(v)).sum::<i32>().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(input
+            .iter()
+            .map(|v| extrapolate_left(v))
+            .sum::<i32>()
+            .into())
+    }
+}
+
+impl Generate for Day9 {
+    // Values of polynomials with small integer coefficients, so that the
+    // differences reach zero and the extrapolated values fit an `i32`.
+    fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+        let mut r = String::new();
+        for _ in 0..size.max(1) {
+            let coefficients: Vec<i32> = (0..rng.gen_range(1..=5))
+                .map(|_| rng.gen_range(-4..=4))
+                .collect();
+            let values: Vec<String> = (0..21)
+                .map(|x| coefficients.iter().rev().fold(0, |acc, c| acc * x + c))
+                .map(|v| v.to_string())
+                .collect();
+            r += &values.join(" ");
+            r.push('\n');
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::solve_generated, solve, Part};
+
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            solve::<Day9>(EXAMPLE, Part::One).unwrap(),
+            Answer::Number(114)
+        );
+        assert_eq!(
+            solve::<Day9>(EXAMPLE, Part::Two).unwrap(),
+            Answer::Number(2)
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            solve::<Day9>(input, Part::One).unwrap(),
+            Answer::Number(1992273652)
+        );
+        assert_eq!(
+            solve::<Day9>(input, Part::Two).unwrap(),
+            Answer::Number(1012)
+        );
+    }
+
+    #[test]
+    fn generated() {
+        solve_generated::<Day9>(0..10, 200).unwrap();
+    }
+}